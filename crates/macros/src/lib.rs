@@ -7,24 +7,92 @@
 */
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, ItemEnum, ItemFn};
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, ItemEnum, ItemFn, LitBool, LitInt, LitStr, ReturnType, Type};
 
+/// Logs the duration of the annotated function at debug level, through a `debug!` macro in scope at the call site.
+///
+/// Accepted arguments, all optional:
+/// - `name = "redis.geo_search"` (or just `"redis.geo_search"`) - label to log, the function name by default.
+/// - `track_result` - also logs an `ok` outcome for functions not returning a `Result`, whose outcome is always logged.
+/// - `enabled = false` - emits the function untouched.
+///
+/// The timing is compiled in through the `measure-duration` feature, enabled by default. Without it, for instance with
+/// `default-features = false` in every crate depending on this one, every annotated function is emitted untouched.
 #[proc_macro_attribute]
-pub fn measure_duration(_: TokenStream, input: TokenStream) -> TokenStream {
+pub fn measure_duration(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut track_result = false;
+    let mut name: Option<LitStr> = None;
+    let mut enabled = true;
+    // A lone string literal, e.g. `#[measure_duration("redis.geo_search")]`, is shorthand for `name = "..."`.
+    if let Ok(literal) = syn::parse::<LitStr>(attr.clone()) {
+        name = Some(literal);
+    } else {
+        let args_parser = syn::meta::parser(|meta| {
+            if meta.path.is_ident("track_result") {
+                track_result = true;
+                Ok(())
+            } else if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("enabled") {
+                enabled = meta.value()?.parse::<LitBool>()?.value;
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unsupported measure_duration argument, expected `track_result`, `name = \"...\"` or `enabled = ...`",
+                ))
+            }
+        });
+        parse_macro_input!(attr with args_parser);
+    }
+
     let input_fn = parse_macro_input!(input as ItemFn);
-    let function_body = &input_fn.block;
+
+    // With `enabled = false`, or without the `measure-duration` feature, the function is emitted untouched, so the
+    // annotation can stay in place at no runtime cost.
+    if !enabled || !cfg!(feature = "measure-duration") {
+        return TokenStream::from(quote! { #input_fn });
+    }
+
     let fn_name = &input_fn.sig.ident;
-    let args = &input_fn.sig.inputs;
     let return_type = &input_fn.sig.output;
+    let visibility = &input_fn.vis;
+    let attributes = &input_fn.attrs;
+    // The signature is kept as is, so asyncness, generics, the where clause and any receiver are preserved.
+    let signature = &input_fn.sig;
+    let label = match &name {
+        Some(name) => quote! { #name },
+        None => quote! { stringify!(#fn_name) },
+    };
+    let run_body = run_body(&input_fn);
+
+    // The outcome of a function returning a `Result` is always logged, while other functions cannot fail and are only
+    // reported as `ok` with `track_result`.
+    let outcome = if returns_result(return_type) {
+        Some(quote! { if result.is_ok() { "ok" } else { "err" } })
+    } else if track_result {
+        Some(quote! { "ok" })
+    } else {
+        None
+    };
+    let log_duration = match outcome {
+        Some(outcome) => quote! {
+            debug!("Function: {} | Duration (ms): {} | Outcome: {}", #label, elapsed_ms, #outcome);
+        },
+        None => quote! {
+            debug!("Function: {} | Duration (ms): {}", #label, elapsed_ms);
+        },
+    };
 
     let expanded = quote! {
-        pub async fn #fn_name(#args) #return_type {
+        #(#attributes)*
+        #visibility #signature {
             let start_time = std::time::Instant::now();
-            let result = #function_body;
+            let result = #run_body;
             let elapsed_time = start_time.elapsed();
             let elapsed_ms = elapsed_time.as_secs() * 1000 + u64::from(elapsed_time.subsec_millis());
-            debug!("Function: {} | Duration (ms): {}", stringify!(#fn_name), elapsed_ms);
+            #log_duration
             result
         }
     };
@@ -32,34 +100,126 @@ pub fn measure_duration(_: TokenStream, input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+// The body is run as a block of its own, awaited as a whole for an async function, including methods taking `self`,
+// and called as a closure otherwise. So a `return` or `?` in the body only leaves that block, and the code following it
+// still runs once all the work of the body has completed.
+fn run_body(input_fn: &ItemFn) -> proc_macro2::TokenStream {
+    let function_body = &input_fn.block;
+    match input_fn.sig.asyncness {
+        Some(_) => quote! { async move #function_body.await },
+        None => quote! { (|| #function_body)() },
+    }
+}
+
+fn returns_result(return_type: &ReturnType) -> bool {
+    match return_type {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+/// Profiles the annotated function when the `ENABLE_FLAMEGRAPH` environment variable is set to `1` or `true` (in any
+/// case), writing `<fn>-flamegraph.svg` and `<fn>-profiling.prof` to the output directory. Any other value, e.g. `0`
+/// or `false`, leaves profiling off.
+///
+/// Accepted arguments, all optional:
+/// - `frequency = 250` - sampling frequency in Hz, `1000` by default.
+/// - `output_dir = "/tmp/prof"` (or `dir = "..."`) - directory the reports are written to, `./profiling` by default.
+/// - `prof = false` - skips writing the `.prof` report, `true` by default.
+///
+/// The profiler samples CPU time, so a function spending its time awaiting, e.g. on I/O or timers, yields few or no
+/// samples. When there are none, no report is written and the failure is logged instead.
+///
+/// The expansion refers to `pprof` and `tracing` by path, so both must be dependencies of the calling crate, with
+/// the `flamegraph` feature of `pprof` enabled.
 #[proc_macro_attribute]
-pub fn generate_flamegraph(_: TokenStream, input: TokenStream) -> TokenStream {
+pub fn generate_flamegraph(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut frequency: i32 = 1000;
+    let mut output_dir = LitStr::new("./profiling", proc_macro2::Span::call_site());
+    let mut write_prof = true;
+    let args_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("frequency") {
+            let value: LitInt = meta.value()?.parse()?;
+            frequency = match value.base10_parse::<i32>() {
+                Ok(frequency) if frequency > 0 => frequency,
+                _ => {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "frequency must be a positive integer, in Hz",
+                    ))
+                }
+            };
+            Ok(())
+        } else if meta.path.is_ident("output_dir") || meta.path.is_ident("dir") {
+            output_dir = meta.value()?.parse()?;
+            Ok(())
+        } else if meta.path.is_ident("prof") {
+            write_prof = meta.value()?.parse::<LitBool>()?.value;
+            Ok(())
+        } else {
+            Err(meta.error(
+                "unsupported generate_flamegraph argument, expected `frequency = ...`, `output_dir = \"...\"`, `dir = \"...\"` or `prof = ...`",
+            ))
+        }
+    });
+    parse_macro_input!(attr with args_parser);
+
     let input_fn = parse_macro_input!(input as ItemFn);
-    let function_body = &input_fn.block;
     let fn_name = &input_fn.sig.ident;
-    let args = &input_fn.sig.inputs;
-    let return_type = &input_fn.sig.output;
     let visibility = &input_fn.vis;
-    let asyncness = input_fn.sig.asyncness;
-
-    let function_start = match (asyncness, visibility) {
-        (Some(_), _) => quote! { pub async fn },
-        (None, syn::Visibility::Public(_)) => quote! { pub fn },
-        (None, _) => quote! { fn },
-    };
+    let attributes = &input_fn.attrs;
+    let signature = &input_fn.sig;
+    let run_body = run_body(&input_fn);
 
     let expanded = quote! {
-        #function_start #fn_name(#args) #return_type {
-            let guard = pprof::ProfilerGuard::new(1000).unwrap();
-            let result = #function_body;
-            if let Ok(report) = guard.report().build() {
-                std::fs::create_dir_all("./profiling").unwrap();
-                let flamegraph_file = std::fs::File::create(format!("./profiling/{}-flamegraph.svg", stringify!(#fn_name))).unwrap();
-                let mut prof_file = std::fs::File::create(format!("./profiling/{}-profiling.prof", stringify!(#fn_name))).unwrap();
-                let _ = report
-                    .flamegraph(flamegraph_file)
-                    .map_err(|err| err.to_string());
-                std::io::Write::write_all(&mut prof_file, format!("{:?}", report).as_bytes()).unwrap();
+        #(#attributes)*
+        #visibility #signature {
+            // Profiling is opt-in through the `ENABLE_FLAMEGRAPH` environment variable, and the function also runs
+            // unprofiled if the profiler cannot be started, e.g. when another guard is active.
+            let enabled = std::env::var("ENABLE_FLAMEGRAPH")
+                .is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"));
+            let guard = match enabled {
+                true => match pprof::ProfilerGuard::new(#frequency) {
+                    Ok(guard) => Some(guard),
+                    Err(err) => {
+                        tracing::warn!("Failed to start profiling {} : {}", stringify!(#fn_name), err);
+                        None
+                    }
+                },
+                false => None,
+            };
+            let result = #run_body;
+            if let Some(Ok(report)) = guard.as_ref().map(|guard| guard.report().build()) {
+                // Failing to write the report must not affect the profiled function, so errors are only logged.
+                let write_report = || -> std::io::Result<()> {
+                    // Without any sample the flamegraph would be an empty file, so nothing is written.
+                    if report.data.is_empty() {
+                        return Err(std::io::Error::other("no samples were collected"));
+                    }
+                    let output_dir = std::path::Path::new(#output_dir);
+                    std::fs::create_dir_all(output_dir)?;
+                    let flamegraph_path = output_dir.join(format!("{}-flamegraph.svg", stringify!(#fn_name)));
+                    let flamegraph_file = std::fs::File::create(&flamegraph_path)?;
+                    if let Err(err) = report.flamegraph(flamegraph_file) {
+                        let _ = std::fs::remove_file(&flamegraph_path);
+                        return Err(std::io::Error::other(err.to_string()));
+                    }
+                    if #write_prof {
+                        let mut prof_file = std::fs::File::create(output_dir.join(format!("{}-profiling.prof", stringify!(#fn_name))))?;
+                        std::io::Write::write_all(&mut prof_file, format!("{:?}", report).as_bytes())?;
+                    }
+                    Ok(())
+                };
+                if let Err(err) = write_report() {
+                    tracing::error!("Failed to write the flamegraph of {} : {}", stringify!(#fn_name), err);
+                }
             };
             result
         }
@@ -73,14 +233,58 @@ pub fn add_error(_: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as ItemEnum);
     let enum_name = &input.ident;
 
-    let variants = input.variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-        let variant_screaming_snake_case = convert_to_snake_case(variant_name.to_string());
-        quote! {
-            #[error(#variant_screaming_snake_case)]
-            #variant,
+    let mut variants = Vec::with_capacity(input.variants.len());
+    for variant in input.variants.iter() {
+        let mut variant = variant.clone();
+        // A `#[msg("...")]` or `#[msg = "..."]` attribute overrides the default SCREAMING_SNAKE_CASE message, and is
+        // consumed here as it is not a real attribute.
+        let message = match variant
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("msg"))
+        {
+            Some(idx) => match parse_message(&variant.attrs.remove(idx)) {
+                Ok(message) => message,
+                Err(err) => return err.to_compile_error().into(),
+            },
+            None => LitStr::new(
+                &convert_to_snake_case(variant.ident.to_string()),
+                variant.ident.span(),
+            ),
+        };
+        // A `#[from]` attribute on the variant is moved to its single field, so that thiserror generates the `From`
+        // conversion for the source error.
+        if let Some(idx) = variant
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("from"))
+        {
+            let attr = variant.attrs.remove(idx);
+            if variant.fields.len() != 1 {
+                return syn::Error::new_spanned(
+                    attr,
+                    "#[from] requires a variant with a single field",
+                )
+                .to_compile_error()
+                .into();
+            }
+            if let Some(field) = variant.fields.iter_mut().next() {
+                field.attrs.push(attr);
+            }
         }
-    });
+        // Source errors are rarely `Serialize`, so they are skipped when serializing the error.
+        for field in variant.fields.iter_mut() {
+            let is_source = field.attrs.iter().any(|attr| attr.path().is_ident("from"));
+            let has_serde = field.attrs.iter().any(|attr| attr.path().is_ident("serde"));
+            if is_source && !has_serde {
+                field.attrs.push(syn::parse_quote! { #[serde(skip)] });
+            }
+        }
+        variants.push(quote! {
+            #[error(#message)]
+            #variant,
+        });
+    }
 
     let expanded = quote! {
         #[derive(Debug, Serialize, thiserror::Error)]
@@ -92,6 +296,13 @@ pub fn add_error(_: TokenStream, input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+fn parse_message(attr: &syn::Attribute) -> syn::Result<LitStr> {
+    match &attr.meta {
+        syn::Meta::NameValue(name_value) => syn::parse2(name_value.value.to_token_stream()),
+        _ => attr.parse_args(),
+    }
+}
+
 fn convert_to_snake_case(input: String) -> String {
     let mut result = String::new();
     let mut last_char_was_upper = false;
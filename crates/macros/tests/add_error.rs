@@ -0,0 +1,72 @@
+/*  Copyright 2022-23, Juspay India Pvt Ltd
+    This program is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License
+    as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version. This program
+    is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+    or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details. You should have received a copy of
+    the GNU Affero General Public License along with this program. If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use serde::Serialize;
+
+#[macros::add_error]
+enum DriverError {
+    DriverNotFound(String),
+    #[msg("Driver {0} is already on a ride")]
+    DriverOnRide(String),
+    #[msg("Invalid location")]
+    InvalidLocation,
+    #[msg = "Ride {0} was cancelled"]
+    RideCancelled(String),
+}
+
+#[macros::add_error]
+enum ConfigError {
+    #[from]
+    #[msg("Invalid TTL : {0}")]
+    InvalidTtl(std::num::ParseIntError),
+}
+
+fn parse_ttl(ttl: &str) -> Result<u32, ConfigError> {
+    Ok(ttl.parse::<u32>()?)
+}
+
+#[test]
+fn display_uses_the_msg_attribute_or_the_variant_name() {
+    assert_eq!(
+        DriverError::DriverNotFound("driver".to_string()).to_string(),
+        "DRIVER_NOT_FOUND"
+    );
+    assert_eq!(
+        DriverError::DriverOnRide("driver".to_string()).to_string(),
+        "Driver driver is already on a ride"
+    );
+    assert_eq!(DriverError::InvalidLocation.to_string(), "Invalid location");
+}
+
+#[test]
+fn display_uses_the_msg_name_value_attribute() {
+    assert_eq!(
+        DriverError::RideCancelled("ride".to_string()).to_string(),
+        "Ride ride was cancelled"
+    );
+}
+
+#[test]
+fn from_converts_the_source_error() {
+    let err = parse_ttl("never").err();
+
+    assert!(matches!(err, Some(ConfigError::InvalidTtl(_))));
+    assert_eq!(
+        err.map(|err| err.to_string()).as_deref(),
+        Some("Invalid TTL : invalid digit found in string")
+    );
+}
+
+#[test]
+fn from_source_error_is_skipped_when_serializing() {
+    let json = parse_ttl("never")
+        .err()
+        .and_then(|err| serde_json::to_string(&err).ok());
+
+    assert_eq!(json.as_deref(), Some(r#""InvalidTtl""#));
+}
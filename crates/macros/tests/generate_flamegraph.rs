@@ -0,0 +1,66 @@
+/*  Copyright 2022-23, Juspay India Pvt Ltd
+    This program is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License
+    as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version. This program
+    is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+    or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details. You should have received a copy of
+    the GNU Affero General Public License along with this program. If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use std::{fs, path::Path};
+
+#[macros::generate_flamegraph(dir = "target/flamegraph-test")]
+async fn busy_sum(iterations: u64) -> u64 {
+    let mut sum: u64 = 0;
+    for i in 0..iterations {
+        sum = std::hint::black_box(sum.wrapping_add(i.wrapping_mul(i)));
+    }
+    sum
+}
+
+#[macros::generate_flamegraph(dir = "target/flamegraph-test")]
+fn busy_check(iterations: u64) -> Result<u64, String> {
+    let mut sum: u64 = 0;
+    for i in 0..iterations {
+        sum = std::hint::black_box(sum.wrapping_add(i.wrapping_mul(i)));
+    }
+    let sum = u32::try_from(sum).map_err(|err| err.to_string())?;
+    Ok(u64::from(sum))
+}
+
+#[macros::generate_flamegraph(dir = "target/flamegraph-test")]
+async fn idle() {}
+
+// The cases share a test, as only one profiler can be active at a time.
+#[test]
+fn flamegraph_is_written_only_with_samples() {
+    let flamegraph = Path::new("target/flamegraph-test/busy_sum-flamegraph.svg");
+    let early_return_flamegraph = Path::new("target/flamegraph-test/busy_check-flamegraph.svg");
+    let empty_flamegraph = Path::new("target/flamegraph-test/idle-flamegraph.svg");
+    let _ = fs::remove_file(flamegraph);
+    let _ = fs::remove_file(early_return_flamegraph);
+    let _ = fs::remove_file(empty_flamegraph);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap_or_else(|err| panic!("Failed to build the runtime : {err}"));
+
+    std::env::set_var("ENABLE_FLAMEGRAPH", "false");
+    runtime.block_on(busy_sum(100_000_000));
+    assert!(!flamegraph.exists());
+
+    std::env::set_var("ENABLE_FLAMEGRAPH", "1");
+    runtime.block_on(busy_sum(300_000_000));
+
+    let size = fs::metadata(flamegraph)
+        .map(|metadata| metadata.len())
+        .unwrap_or_else(|err| panic!("Flamegraph was not written : {err}"));
+    assert!(size > 0);
+
+    assert!(busy_check(300_000_000).is_err());
+    let size = fs::metadata(early_return_flamegraph)
+        .map(|metadata| metadata.len())
+        .unwrap_or_else(|err| panic!("Flamegraph was not written on early return : {err}"));
+    assert!(size > 0);
+
+    runtime.block_on(idle());
+    assert!(!empty_flamegraph.exists());
+}
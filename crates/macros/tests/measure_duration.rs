@@ -0,0 +1,159 @@
+/*  Copyright 2022-23, Juspay India Pvt Ltd
+    This program is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License
+    as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version. This program
+    is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+    or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details. You should have received a copy of
+    the GNU Affero General Public License along with this program. If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use std::{
+    fmt::Display,
+    future::Future,
+    io,
+    sync::{Arc, Mutex},
+};
+#[cfg_attr(not(feature = "measure-duration"), allow(unused_imports))]
+use tracing::debug;
+
+/// Whether the timing is compiled in, as the functions are emitted untouched without the `measure-duration` feature.
+const TIMED: bool = cfg!(feature = "measure-duration");
+
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .map_err(|err| io::Error::other(err.to_string()))?
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs `f` with the debug logs captured, returning its output along with the logs.
+fn with_logs<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let output = tracing::subscriber::with_default(subscriber, f);
+    let logs =
+        String::from_utf8_lossy(&logs.0.lock().unwrap_or_else(|err| err.into_inner())).to_string();
+    (output, logs)
+}
+
+/// Runs a future to completion on the current thread, so that it sees the subscriber of `with_logs`.
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map(|runtime| runtime.block_on(future))
+        .unwrap_or_else(|err| panic!("Failed to build the runtime : {err}"))
+}
+
+#[macros::measure_duration]
+async fn describe_async<T>(value: T) -> String
+where
+    T: Display + Send,
+{
+    format!("value {value}")
+}
+
+#[macros::measure_duration]
+fn describe<T: Display>(value: T) -> String {
+    format!("value {value}")
+}
+
+struct Config {
+    ttl: String,
+}
+
+impl Config {
+    #[macros::measure_duration]
+    async fn ttl(&self) -> Result<u32, String> {
+        let ttl = self.ttl.parse::<u32>().map_err(|err| err.to_string())?;
+        Ok(ttl * 60)
+    }
+}
+
+#[test]
+fn async_method_on_self() {
+    let config = Config {
+        ttl: "5".to_string(),
+    };
+    let (output, logs) = with_logs(|| block_on(config.ttl()));
+
+    assert_eq!(output, Ok(300));
+    assert_eq!(
+        logs.contains("Function: ttl | Duration (ms): "),
+        TIMED,
+        "{logs}"
+    );
+    assert_eq!(logs.contains("| Outcome: ok"), TIMED, "{logs}");
+}
+
+#[test]
+fn async_method_returning_early() {
+    let config = Config {
+        ttl: "never".to_string(),
+    };
+    let (output, logs) = with_logs(|| block_on(config.ttl()));
+
+    assert!(output.is_err());
+    assert_eq!(
+        logs.contains("Function: ttl | Duration (ms): "),
+        TIMED,
+        "{logs}"
+    );
+    assert_eq!(logs.contains("| Outcome: err"), TIMED, "{logs}");
+}
+
+#[macros::measure_duration]
+fn parse_ttl(ttl: &str) -> Result<u32, String> {
+    let ttl = ttl.parse::<u32>().map_err(|err| err.to_string())?;
+    Ok(ttl * 60)
+}
+
+#[test]
+fn sync_fn_returning_early() {
+    let (output, logs) = with_logs(|| parse_ttl("never"));
+
+    assert!(output.is_err());
+    assert_eq!(
+        logs.contains("Function: parse_ttl | Duration (ms): "),
+        TIMED,
+        "{logs}"
+    );
+    assert_eq!(logs.contains("| Outcome: err"), TIMED, "{logs}");
+}
+
+#[test]
+fn generic_async_fn_with_where_clause() {
+    let (output, logs) = with_logs(|| block_on(describe_async(42)));
+
+    assert_eq!(output, "value 42");
+    assert_eq!(
+        logs.contains("Function: describe_async | Duration (ms): "),
+        TIMED,
+        "{logs}"
+    );
+}
+
+#[test]
+fn generic_sync_fn() {
+    let (output, logs) = with_logs(|| describe("driver"));
+
+    assert_eq!(output, "value driver");
+    assert_eq!(
+        logs.contains("Function: describe | Duration (ms): "),
+        TIMED,
+        "{logs}"
+    );
+}
@@ -0,0 +1,27 @@
+/*  Copyright 2022-23, Juspay India Pvt Ltd
+    This program is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License
+    as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version. This program
+    is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+    or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details. You should have received a copy of
+    the GNU Affero General Public License along with this program. If not, see <https://www.gnu.org/licenses/>.
+*/
+
+
+mod drivers {
+    use tracing::debug;
+
+    #[macros::measure_duration]
+    fn private_helper() -> u32 {
+        1
+    }
+
+    #[macros::measure_duration]
+    pub fn public_helper() -> u32 {
+        private_helper()
+    }
+}
+
+fn main() {
+    drivers::public_helper();
+    drivers::private_helper();
+}
@@ -0,0 +1,23 @@
+/*  Copyright 2022-23, Juspay India Pvt Ltd
+    This program is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License
+    as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version. This program
+    is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+    or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details. You should have received a copy of
+    the GNU Affero General Public License along with this program. If not, see <https://www.gnu.org/licenses/>.
+*/
+
+
+#[macros::generate_flamegraph(frequency = 250, dir = "/tmp/prof")]
+fn configured() -> u32 {
+    1
+}
+
+#[macros::generate_flamegraph]
+async fn defaults() -> u32 {
+    2
+}
+
+fn main() {
+    let _ = configured();
+    let _ = defaults();
+}
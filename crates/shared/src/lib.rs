@@ -10,4 +10,5 @@
 #![deny(clippy::expect_used)]
 #![deny(clippy::panic)]
 
+pub mod prometheus;
 pub mod redis;
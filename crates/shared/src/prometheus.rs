@@ -0,0 +1,212 @@
+/*  Copyright 2022-23, Juspay India Pvt Ltd
+    This program is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License
+    as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version. This program
+    is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+    or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details. You should have received a copy of
+    the GNU Affero General Public License along with this program. If not, see <https://www.gnu.org/licenses/>.
+*/
+
+#![allow(clippy::expect_used)]
+
+pub use once_cell::sync::Lazy;
+use prometheus::{
+    core::{Collector, MetricVec, MetricVecBuilder},
+    histogram_opts, register_histogram_vec, HistogramTimer,
+};
+pub use prometheus::{CounterVec, GaugeVec, HistogramOpts, HistogramVec, Opts, DEFAULT_BUCKETS};
+
+/// A metric vector registered in the default registry, whose label values are checked to be exactly `N` at compile time.
+///
+/// It is created through `define_histogram!`, `define_counter!` or `define_gauge!`.
+pub struct LabelledMetric<V, const N: usize>(V);
+
+impl<T, const N: usize> LabelledMetric<MetricVec<T>, N>
+where
+    T: MetricVecBuilder,
+    MetricVec<T>: Collector + Clone + 'static,
+{
+    /// Registers the metric vector in the default registry.
+    ///
+    /// Invalid metrics, e.g. defined twice with the same name, are programming errors, so this panics on failure like
+    /// the `register_*!` macros of prometheus.
+    #[doc(hidden)]
+    #[allow(clippy::panic)]
+    pub fn register(name: &str, metric: prometheus::Result<MetricVec<T>>) -> Self {
+        let metric = metric
+            .and_then(|metric| prometheus::register(Box::new(metric.clone())).map(|_| metric))
+            .unwrap_or_else(|err| panic!("Failed to register {name} metrics : {err}"));
+        Self(metric)
+    }
+
+    /// The metric for the given label values, e.g. to `observe` a histogram or `inc` a counter.
+    pub fn with_labels(&self, labels: [&str; N]) -> T::M {
+        self.0.with_label_values(&labels)
+    }
+
+    /// The underlying metric vector.
+    pub fn inner(&self) -> &MetricVec<T> {
+        &self.0
+    }
+}
+
+/// Defines a `HistogramVec` static registered in the default registry on first use, named after the lowercased static.
+///
+/// ```ignore
+/// define_histogram!(pub RIDE_SEARCH, "Ride searches", &["city", "vehicle"]);
+/// define_histogram!(pub RIDE_SEARCH_LATENCY, "Ride search latency", &["city"], vec![0.01, 0.1, 1.0]);
+///
+/// RIDE_SEARCH.with_labels(["blr", "auto"]).observe(1.0);
+/// ```
+///
+/// The number of label values is checked at compile time. Use `Lazy::force` at startup to export the metric before
+/// its first use.
+#[macro_export]
+macro_rules! define_histogram {
+    ($vis:vis $name:ident, $help:expr, &[$($label:literal),* $(,)?] $(,)?) => {
+        $crate::define_histogram!(
+            $vis $name,
+            $help,
+            &[$($label),*],
+            $crate::prometheus::DEFAULT_BUCKETS.to_vec()
+        );
+    };
+    ($vis:vis $name:ident, $help:expr, &[$($label:literal),* $(,)?], $buckets:expr $(,)?) => {
+        $vis static $name: $crate::prometheus::Lazy<
+            $crate::prometheus::LabelledMetric<
+                $crate::prometheus::HistogramVec,
+                { <[&str]>::len(&[$($label),*]) },
+            >,
+        > = $crate::prometheus::Lazy::new(|| {
+            let name = stringify!($name).to_lowercase();
+            $crate::prometheus::LabelledMetric::register(
+                &name,
+                $crate::prometheus::HistogramVec::new(
+                    $crate::prometheus::HistogramOpts::new(name.as_str(), $help).buckets($buckets),
+                    &[$($label),*],
+                ),
+            )
+        });
+    };
+}
+
+/// Defines a `CounterVec` static registered in the default registry on first use, named after the lowercased static.
+///
+/// See `define_histogram!`.
+#[macro_export]
+macro_rules! define_counter {
+    ($vis:vis $name:ident, $help:expr, &[$($label:literal),* $(,)?] $(,)?) => {
+        $vis static $name: $crate::prometheus::Lazy<
+            $crate::prometheus::LabelledMetric<
+                $crate::prometheus::CounterVec,
+                { <[&str]>::len(&[$($label),*]) },
+            >,
+        > = $crate::prometheus::Lazy::new(|| {
+            let name = stringify!($name).to_lowercase();
+            $crate::prometheus::LabelledMetric::register(
+                &name,
+                $crate::prometheus::CounterVec::new(
+                    $crate::prometheus::Opts::new(name.as_str(), $help),
+                    &[$($label),*],
+                ),
+            )
+        });
+    };
+}
+
+/// Defines a `GaugeVec` static registered in the default registry on first use, named after the lowercased static.
+///
+/// See `define_histogram!`.
+#[macro_export]
+macro_rules! define_gauge {
+    ($vis:vis $name:ident, $help:expr, &[$($label:literal),* $(,)?] $(,)?) => {
+        $vis static $name: $crate::prometheus::Lazy<
+            $crate::prometheus::LabelledMetric<
+                $crate::prometheus::GaugeVec,
+                { <[&str]>::len(&[$($label),*]) },
+            >,
+        > = $crate::prometheus::Lazy::new(|| {
+            let name = stringify!($name).to_lowercase();
+            $crate::prometheus::LabelledMetric::register(
+                &name,
+                $crate::prometheus::GaugeVec::new(
+                    $crate::prometheus::Opts::new(name.as_str(), $help),
+                    &[$($label),*],
+                ),
+            )
+        });
+    };
+}
+
+/// Latency of the operations of `RedisConnectionPool`, in seconds, labelled by the name of the operation.
+pub static REDIS_OPERATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        histogram_opts!(
+            "redis_operation_duration_seconds",
+            "Redis operations",
+            vec![0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]
+        ),
+        &["command"]
+    )
+    .expect("Failed to register redis operation duration metrics")
+});
+
+/// Starts timing a Redis operation, the duration is observed in `REDIS_OPERATION` when the timer is dropped.
+pub(crate) fn redis_operation_timer(command: &str) -> HistogramTimer {
+    REDIS_OPERATION.with_label_values(&[command]).start_timer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    define_histogram!(TEST_HISTOGRAM, "Test histogram", &["city", "vehicle"]);
+    define_histogram!(
+        TEST_BUCKETED_HISTOGRAM,
+        "Test histogram",
+        &["city"],
+        vec![1.0, 2.0]
+    );
+    define_counter!(TEST_COUNTER, "Test counter", &["city"]);
+    define_gauge!(TEST_GAUGE, "Test gauge", &[]);
+
+    #[test]
+    fn defined_metrics_are_registered() {
+        TEST_HISTOGRAM.with_labels(["blr", "auto"]).observe(1.5);
+        TEST_BUCKETED_HISTOGRAM.with_labels(["blr"]).observe(1.5);
+        TEST_COUNTER.with_labels(["blr"]).inc();
+        TEST_GAUGE.with_labels([]).set(3.0);
+
+        let registered = prometheus::gather()
+            .into_iter()
+            .map(|family| family.get_name().to_string())
+            .collect::<Vec<String>>();
+        for name in [
+            "test_histogram",
+            "test_bucketed_histogram",
+            "test_counter",
+            "test_gauge",
+        ] {
+            assert!(
+                registered.iter().any(|registered| registered == name),
+                "{name}"
+            );
+        }
+        assert_eq!(
+            TEST_HISTOGRAM
+                .with_labels(["blr", "auto"])
+                .get_sample_count(),
+            1
+        );
+        assert_eq!(TEST_GAUGE.with_labels([]).get(), 3.0);
+    }
+
+    #[test]
+    fn redis_operation_timer_observes_on_drop() {
+        let histogram = REDIS_OPERATION.with_label_values(&["test_operation"]);
+        let before = histogram.get_sample_count();
+
+        drop(redis_operation_timer("test_operation"));
+
+        assert_eq!(histogram.get_sample_count(), before + 1);
+    }
+}
@@ -7,25 +7,73 @@
 */
 #![allow(clippy::unwrap_used)]
 
+use crate::prometheus::redis_operation_timer;
 use crate::redis::error::RedisError;
 use crate::redis::types::*;
 use fred::{
     interfaces::{
-        GeoInterface, HashesInterface, KeysInterface, SortedSetsInterface, StreamsInterface,
+        ClientLike, GeoInterface, HashesInterface, HyperloglogInterface, KeysInterface,
+        LuaInterface, PubsubInterface, SortedSetsInterface, StreamsInterface,
     },
     prelude::ListInterface,
     types::{
-        Expiration, FromRedis, GeoPosition, GeoRadiusInfo, GeoUnit, GeoValue, Limit,
-        MultipleGeoValues, MultipleKeys, Ordering, RedisKey, RedisMap, RedisValue, SetOptions,
-        SortOrder, StringOrNumber, XCapKind, XCapTrim, ZSort,
+        ClusterHash, CustomCommand, Expiration, FromRedis, GeoPosition, GeoRadiusInfo, GeoUnit,
+        GeoValue, Limit, MultipleGeoValues, MultipleKeys, Ordering, RedisKey, RedisMap, RedisValue,
+        Scanner, SetOptions, SortOrder, StringOrNumber, XCap, XCapKind, XCapTrim, ZSort,
         XID::{self, Auto, Manual},
     },
 };
+use futures::{stream, Stream, StreamExt};
+use rand::{distributions::Alphanumeric, Rng};
 use rustc_hash::FxHashMap;
 use serde::{de::DeserializeOwned, Serialize};
-use std::{fmt::Debug, ops::Deref};
+use std::{
+    fmt::Debug,
+    ops::Deref,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 use tracing::error;
 
+const RATE_LIMIT_SCRIPT: &str = r#"
+local max_tokens = tonumber(ARGV[1])
+local refill_per_sec = tonumber(ARGV[2])
+local time = redis.call("TIME")
+local now = tonumber(time[1]) + tonumber(time[2]) / 1000000
+local bucket = redis.call("HMGET", KEYS[1], "tokens", "timestamp")
+local tokens = tonumber(bucket[1]) or max_tokens
+local timestamp = tonumber(bucket[2]) or now
+tokens = math.min(max_tokens, tokens + math.max(0, now - timestamp) * refill_per_sec)
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+redis.call("HSET", KEYS[1], "tokens", tostring(tokens), "timestamp", tostring(now))
+redis.call("EXPIRE", KEYS[1], math.max(1, math.ceil(max_tokens / refill_per_sec)))
+return allowed
+"#;
+
+/// Sets the field `ARGV[2]` of the hash `KEYS[1]` to `ARGV[3]`, setting an expiry of `ARGV[1]` seconds only if the hash
+/// was created by this write. Returns `1` if the field is new, `0` if it was updated.
+const SET_HASH_FIELD_SCRIPT: &str = r#"
+local created = redis.call("EXISTS", KEYS[1]) == 0
+local added = redis.call("HSET", KEYS[1], ARGV[2], ARGV[3])
+if created then
+    redis.call("EXPIRE", KEYS[1], ARGV[1])
+end
+return added
+"#;
+
+/// Increments `KEYS[1]` by `ARGV[1]`, setting an expiry of `ARGV[2]` seconds only if the key was created by this increment.
+const INCR_BY_WITH_EXPIRY_SCRIPT: &str = r#"
+local created = redis.call("EXISTS", KEYS[1]) == 0
+local value = redis.call("INCRBY", KEYS[1], ARGV[1])
+if created then
+    redis.call("EXPIRE", KEYS[1], ARGV[2])
+end
+return value
+"#;
+
 impl RedisConnectionPool {
     /// Asynchronously sets a key-value pair in a Redis datastore with an expiry time.
     ///
@@ -50,43 +98,67 @@ impl RedisConnectionPool {
     /// * If there is a failure in setting the value associated with the key in Redis.
     /// * If the value type `V` fails to convert into `RedisValue`.
     pub async fn set_key<V>(&self, key: &str, value: V, expiry: u32) -> Result<(), RedisError>
+    where
+        V: Serialize + Send + Sync,
+    {
+        let _timer = redis_operation_timer("set_key");
+        self.set_serialized(key, value, expiry).await
+    }
+
+    /// Sets a key to the JSON serialized value, expiring after the `default_ttl` (from the `RedisSettings`).
+    pub async fn set_key_with_default_ttl<V>(&self, key: &str, value: V) -> Result<(), RedisError>
+    where
+        V: Serialize + Send + Sync,
+    {
+        let _timer = redis_operation_timer("set_key_with_default_ttl");
+        self.set_serialized(key, value, self.default_ttl).await
+    }
+
+    async fn set_serialized<V>(&self, key: &str, value: V, expiry: u32) -> Result<(), RedisError>
     where
         V: Serialize + Send + Sync,
     {
         let serialized_value = serde_json::to_string(&value)
             .map_err(|err| RedisError::SerializationError(err.to_string()))?;
 
-        let redis_value: RedisValue = serialized_value.into();
-
-        self.pool
-            .set(
-                key,
-                redis_value,
-                Some(Expiration::EX(expiry.into())),
-                None,
-                false,
-            )
+        self.set_with_expiry(key, serialized_value.into(), expiry)
             .await
-            .map_err(|err| RedisError::SetFailed(err.to_string()))
     }
 
+    /// Sets a key to the value as is, with an expiry time in seconds.
     pub async fn set_key_as_str(
         &self,
         key: &str,
         value: &str,
         expiry: u32,
     ) -> Result<(), RedisError> {
-        let redis_value: RedisValue = value.into();
+        let _timer = redis_operation_timer("set_key_as_str");
+        self.set_with_expiry(key, value.into(), expiry).await
+    }
+
+    /// Sets a key to the value as is, expiring after the `default_ttl` (from the `RedisSettings`).
+    pub async fn set_key_as_str_with_default_ttl(
+        &self,
+        key: &str,
+        value: &str,
+    ) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("set_key_as_str_with_default_ttl");
+        self.set_with_expiry(key, value.into(), self.default_ttl)
+            .await
+    }
+
+    async fn set_with_expiry(
+        &self,
+        key: &str,
+        value: RedisValue,
+        expiry: u32,
+    ) -> Result<(), RedisError> {
+        let key = &self.add_prefix(key);
+
         self.pool
-            .set(
-                key,
-                redis_value,
-                Some(Expiration::EX(expiry.into())),
-                None,
-                false,
-            )
+            .set(key, value, Some(Expiration::EX(expiry.into())), None, false)
             .await
-            .map_err(|err| RedisError::SetFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::SetFailed))
     }
 
     /// Asynchronously sets a key-value pair in a Redis datastore with an expiry time, only if the key does not already exist.
@@ -123,6 +195,9 @@ impl RedisConnectionPool {
         V: TryInto<RedisValue> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
+        let _timer = redis_operation_timer("setnx_with_expiry");
+        let key = &self.add_prefix(key);
+
         let pipeline = self.pool.pipeline();
 
         let _ = pipeline.msetnx::<RedisValue, _>((key, value)).await;
@@ -131,7 +206,7 @@ impl RedisConnectionPool {
         let output: Vec<RedisValue> = pipeline
             .all()
             .await
-            .map_err(|err| RedisError::SetExFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::SetExFailed))?;
 
         match output.deref() {
             [RedisValue::Integer(1), ..] => Ok(true),
@@ -143,6 +218,58 @@ impl RedisConnectionPool {
         }
     }
 
+    /// Asynchronously acquires a lock on a key in a Redis datastore.
+    ///
+    /// This function sets the key to a random token using `SET key token NX PX ttl_ms`, so the lock is only
+    /// acquired if no one else holds it, and expires on its own if the holder goes away without releasing it.
+    /// The returned `LockGuard` releases the lock when dropped, and only if it still holds the same token.
+    ///
+    /// Note: This is not an implementation of Redlock, it is only safe against a single Redis instance.
+    ///
+    /// # Arguments
+    /// * `key` - A reference to a string representing the key to be locked.
+    /// * `ttl_ms` - The time after which the lock expires, specified in milliseconds.
+    ///
+    /// # Returns
+    /// * `Result<Option<LockGuard>, RedisError>` - Returns `Ok(Some(LockGuard))` if the lock is acquired,
+    ///   `Ok(None)` if it is already held, or an `Err(RedisError::LockFailed)` if the operation fails.
+    pub async fn acquire_lock(
+        &self,
+        key: &str,
+        ttl_ms: u64,
+    ) -> Result<Option<LockGuard>, RedisError> {
+        let _timer = redis_operation_timer("acquire_lock");
+        let key = &self.add_prefix(key);
+
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+
+        let client = self.pool.next().clone();
+
+        let output: RedisValue = client
+            .set(
+                key,
+                token.as_str(),
+                Some(Expiration::PX(ttl_ms as i64)),
+                Some(SetOptions::NX),
+                false,
+            )
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::LockFailed))?;
+
+        match output {
+            RedisValue::String(_) => Ok(Some(LockGuard::new(client, key.to_string(), token))),
+            RedisValue::Null => Ok(None),
+            case => Err(RedisError::LockFailed(format!(
+                "Unexpected RedisValue encountered : {:?}",
+                case
+            ))),
+        }
+    }
+
     /// Asynchronously sets an expiration time for a given key in a Redis datastore.
     ///
     /// This function applies an expiration time to a specified key, causing the key to be
@@ -160,15 +287,109 @@ impl RedisConnectionPool {
     /// # Errors
     /// This function will return an error if there is a failure in applying the expiration time to the key in Redis.
     pub async fn set_expiry(&self, key: &str, seconds: i64) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("set_expiry");
+        self.expire(key, seconds).await
+    }
+
+    async fn expire(&self, key: &str, seconds: i64) -> Result<(), RedisError> {
+        let key = &self.add_prefix(key);
+
         let output: Result<(), _> = self.pool.expire(key, seconds).await;
 
         if let Err(err) = output {
-            Err(RedisError::SetExpiryFailed(err.to_string()))
+            Err(RedisError::from_fred(err, RedisError::SetExpiryFailed))
         } else {
             Ok(())
         }
     }
 
+    /// Asynchronously sets an absolute expiration time for a given key in a Redis datastore.
+    ///
+    /// This function converts the given point in time into a unix timestamp and applies it to the key
+    /// using `EXPIREAT`, causing the key to be automatically deleted at that time.
+    ///
+    /// # Arguments
+    /// * `key` - A reference to a string representing the key to which the expiration time will be applied.
+    /// * `when` - The point in time at which the key will be removed.
+    ///
+    /// # Returns
+    /// * `Result<bool, RedisError>` - Returns `Ok(true)` if the expiration time is set, `Ok(false)` if the key does not exist.
+    ///   Returns an `Err(RedisError::SetExpiryFailed)` containing an error message if the operation fails.
+    ///
+    /// # Errors
+    /// This function will return an error if `when` is before the unix epoch, or if there is a failure in
+    /// applying the expiration time to the key in Redis.
+    pub async fn set_expiry_at(&self, key: &str, when: SystemTime) -> Result<bool, RedisError> {
+        let _timer = redis_operation_timer("set_expiry_at");
+        let key = &self.add_prefix(key);
+
+        let timestamp = when
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| RedisError::SetExpiryFailed(err.to_string()))?
+            .as_secs();
+
+        self.pool
+            .expire_at(key, timestamp as i64)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::SetExpiryFailed))
+    }
+
+    /// Asynchronously removes the expiration time of a given key in a Redis datastore.
+    ///
+    /// # Arguments
+    /// * `key` - A reference to a string representing the key whose expiration time will be removed.
+    ///
+    /// # Returns
+    /// * `Result<bool, RedisError>` - Returns `Ok(true)` if the expiration time is removed, `Ok(false)` if the key
+    ///   does not exist or has no expiration time. Returns an `Err(RedisError::PersistFailed)` if the operation fails.
+    pub async fn persist(&self, key: &str) -> Result<bool, RedisError> {
+        let _timer = redis_operation_timer("persist");
+        let key = &self.add_prefix(key);
+
+        self.pool
+            .persist(key)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::PersistFailed))
+    }
+
+    /// Asynchronously retrieves the remaining time to live of a key in a Redis datastore.
+    ///
+    /// This function issues a `TTL` command for the specified key and maps the raw integer reply
+    /// into a `Ttl`, so callers can decide whether to refresh an expiry without interpreting
+    /// Redis' sentinel values themselves.
+    ///
+    /// # Arguments
+    /// * `key` - A reference to a string representing the key whose TTL is to be fetched.
+    ///
+    /// # Returns
+    /// * `Result<Ttl, RedisError>` - Returns `Ok(Ttl::TtlValue(seconds))` if the key has an expiry,
+    ///   `Ok(Ttl::NoExpiry)` if the key exists without one (`-1`), `Ok(Ttl::NoKeyFound)` if the key
+    ///   does not exist (`-2`), or an `Err(RedisError::TtlFailed)` if the operation fails.
+    ///
+    /// # Errors
+    /// This function will return an error if there is a failure in retrieving the TTL from Redis,
+    /// or if Redis replies with an unexpected value.
+    pub async fn get_ttl(&self, key: &str) -> Result<Ttl, RedisError> {
+        let _timer = redis_operation_timer("get_ttl");
+        let key = &self.add_prefix(key);
+
+        let output: RedisValue = self
+            .pool
+            .ttl(key)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::TtlFailed))?;
+
+        match output {
+            RedisValue::Integer(-1) => Ok(Ttl::NoExpiry),
+            RedisValue::Integer(-2) => Ok(Ttl::NoKeyFound),
+            RedisValue::Integer(ttl) if ttl >= 0 => Ok(Ttl::TtlValue(ttl)),
+            case => Err(RedisError::TtlFailed(format!(
+                "Unexpected RedisValue encountered : {:?}",
+                case
+            ))),
+        }
+    }
+
     /// Asynchronously retrieves the value associated with a specified key in a Redis datastore.
     ///
     /// This function attempts to fetch the value of a specified key from a Redis datastore.
@@ -190,11 +411,14 @@ impl RedisConnectionPool {
     where
         T: DeserializeOwned,
     {
+        let _timer = redis_operation_timer("get_key");
+        let key = &self.add_prefix(key);
+
         let output: RedisValue = self
             .pool
             .get(key)
             .await
-            .map_err(|err| RedisError::GetFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::GetFailed))?;
 
         match output {
             RedisValue::String(val) => serde_json::from_str(&val)
@@ -227,11 +451,14 @@ impl RedisConnectionPool {
     /// - If the Redis query itself fails for any reason (e.g., connection issues).
     /// - If the value retrieved is not a string or is another data type not expected.
     pub async fn get_key_as_str(&self, key: &str) -> Result<Option<String>, RedisError> {
+        let _timer = redis_operation_timer("get_key_as_str");
+        let key = &self.add_prefix(key);
+
         let output: RedisValue = self
             .pool
             .get(key)
             .await
-            .map_err(|err| RedisError::GetFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::GetFailed))?;
 
         match output {
             RedisValue::String(val) => Ok(Some(val.to_string())),
@@ -267,17 +494,21 @@ impl RedisConnectionPool {
     where
         T: DeserializeOwned,
     {
+        let _timer = redis_operation_timer("mget_keys");
         if keys.is_empty() {
             return Ok(vec![]);
         }
 
-        let keys: Vec<RedisKey> = keys.into_iter().map(RedisKey::from).collect();
+        let keys: Vec<RedisKey> = keys
+            .iter()
+            .map(|key| RedisKey::from(self.add_prefix(key)))
+            .collect();
 
         let output: RedisValue = self
             .pool
             .mget(MultipleKeys::from(keys))
             .await
-            .map_err(|err| RedisError::MGetFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::MGetFailed))?;
 
         match output {
             RedisValue::Array(val) => {
@@ -308,6 +539,141 @@ impl RedisConnectionPool {
         }
     }
 
+    /// Asynchronously sets multiple key-value pairs in a Redis datastore with an expiry time.
+    ///
+    /// This function serializes every value and sets each pair with its own `SET ... EX` command, all sent as
+    /// part of a single pipeline. The keys therefore do not need to belong to the same hash slot in cluster mode,
+    /// and no key is ever left without an expiry.
+    ///
+    /// # Type Parameters
+    /// * `V` - The type of the values to be set in the datastore. Must implement `Serialize`.
+    ///
+    /// # Arguments
+    /// * `values` - A vector of key-value pairs to be set in the datastore.
+    /// * `expiry` - The expiration time of every key-value pair, specified in seconds.
+    ///
+    /// # Returns
+    /// * `Result<(), RedisError>` - Returns an `Ok(())` if all the key-value pairs are successfully set,
+    ///   or an `Err(RedisError::MSetFailed)` containing an error message if the operation fails.
+    ///
+    /// # Errors
+    /// This function will return an error:
+    /// * If there is a failure in setting any of the values in Redis.
+    /// * If any of the values fails to serialize, in which case nothing is sent.
+    pub async fn mset_keys<V>(
+        &self,
+        values: Vec<(String, V)>,
+        expiry: u32,
+    ) -> Result<(), RedisError>
+    where
+        V: Serialize,
+    {
+        let _timer = redis_operation_timer("mset_keys");
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let serialized_values = values
+            .iter()
+            .map(|(key, value)| {
+                serde_json::to_string(value)
+                    .map(|value| (self.add_prefix(key), value))
+                    .map_err(|err| RedisError::SerializationError(err.to_string()))
+            })
+            .collect::<Result<Vec<(String, String)>, RedisError>>()?;
+
+        let pipeline = self.pool.pipeline();
+
+        for (key, value) in serialized_values {
+            let _ = pipeline
+                .set::<RedisValue, _, _>(
+                    key,
+                    value,
+                    Some(Expiration::EX(expiry.into())),
+                    None,
+                    false,
+                )
+                .await;
+        }
+
+        pipeline
+            .all::<RedisValue>()
+            .await
+            .map(|_| ())
+            .map_err(|err| RedisError::from_fred(err, RedisError::MSetFailed))
+    }
+
+    /// Atomically increments the integer value of a key in the Redis store by the given amount.
+    ///
+    /// If the key does not exist, it is set to `0` before performing the operation.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key holding the counter.
+    /// - `delta: i64` - The amount by which the counter is incremented.
+    ///
+    /// # Returns
+    /// - `Result<i64, RedisError>` - The value of the counter after the increment (`Ok`),
+    ///   or an `RedisError::IncrFailed` (`Err`) if the key holds a non integer value or the operation fails.
+    pub async fn incr_by(&self, key: &str, delta: i64) -> Result<i64, RedisError> {
+        let _timer = redis_operation_timer("incr_by");
+        let key = &self.add_prefix(key);
+
+        self.pool
+            .incr_by(key, delta)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::IncrFailed))
+    }
+
+    /// Atomically increments the integer value of a key in the Redis store, applying an expiry time when the counter is created.
+    ///
+    /// The expiry is only applied when this increment created the counter, so repeated increments do not keep
+    /// extending the lifetime of the key. The increment and the expiry run in a single Lua script, so a newly created
+    /// counter can never be left without an expiry.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key holding the counter.
+    /// - `delta: i64` - The amount by which the counter is incremented.
+    /// - `expiry: i64` - The expiry time to be applied to a newly created counter, in seconds.
+    ///
+    /// # Returns
+    /// - `Result<i64, RedisError>` - The value of the counter after the increment (`Ok`),
+    ///   or an `RedisError::EvalFailed` (`Err`) if the key holds a non integer value or the script fails.
+    pub async fn incr_by_with_expiry(
+        &self,
+        key: &str,
+        delta: i64,
+        expiry: i64,
+    ) -> Result<i64, RedisError> {
+        let _timer = redis_operation_timer("incr_by_with_expiry");
+        self.eval(
+            INCR_BY_WITH_EXPIRY_SCRIPT,
+            vec![key.to_string()],
+            vec![delta.to_string(), expiry.to_string()],
+        )
+        .await
+    }
+
+    /// Atomically decrements the integer value of a key in the Redis store by the given amount.
+    ///
+    /// If the key does not exist, it is set to `0` before performing the operation.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key holding the counter.
+    /// - `delta: i64` - The amount by which the counter is decremented.
+    ///
+    /// # Returns
+    /// - `Result<i64, RedisError>` - The value of the counter after the decrement (`Ok`),
+    ///   or an `RedisError::DecrFailed` (`Err`) if the key holds a non integer value or the operation fails.
+    pub async fn decr_by(&self, key: &str, delta: i64) -> Result<i64, RedisError> {
+        let _timer = redis_operation_timer("decr_by");
+        let key = &self.add_prefix(key);
+
+        self.pool
+            .decr_by(key, delta)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::DecrFailed))
+    }
+
     /// Deletes a key in the Redis store.
     ///
     /// Given a key, this asynchronous function will attempt to delete it from the Redis store.
@@ -329,16 +695,50 @@ impl RedisConnectionPool {
     /// }
     /// ```
     pub async fn delete_key(&self, key: &str) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("delete_key");
+        let key = &self.add_prefix(key);
+
         self.pool
             .del(key)
             .await
-            .map_err(|err| RedisError::DeleteFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::DeleteFailed))
+    }
+
+    /// Atomically deletes a key only if its current value is the expected one.
+    ///
+    /// The check and the deletion run in a single Lua script, so another client cannot change the key in between,
+    /// e.g. to consume a one-shot token or release a lock only while still holding it.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key to delete.
+    /// - `expected_value: &str` - The value the key must hold, compared with the raw stored value. Note that values set
+    ///   with `set_key` are stored JSON serialized.
+    ///
+    /// # Returns
+    /// - `Result<bool, RedisError>` - `true` if the key held the expected value and was deleted, `false` otherwise (`Ok`),
+    ///   or an `RedisError::EvalFailed` (`Err`) if the script fails.
+    pub async fn compare_and_delete(
+        &self,
+        key: &str,
+        expected_value: &str,
+    ) -> Result<bool, RedisError> {
+        let _timer = redis_operation_timer("compare_and_delete");
+        let deleted: i64 = self
+            .eval(
+                COMPARE_AND_DELETE_SCRIPT,
+                vec![key.to_string()],
+                vec![expected_value.to_string()],
+            )
+            .await?;
+
+        Ok(deleted == 1)
     }
 
     /// Deletes multiple keys in the Redis store as a part of a single pipeline.
     ///
     /// This asynchronous function receives a vector of keys and attempts to delete them all
-    /// from the Redis store in a single pipeline operation. It returns an empty `Result` if all keys
+    /// from the Redis store in a single pipeline operation. Each key is deleted with its own command, so the keys
+    /// do not need to belong to the same hash slot in cluster mode. It returns an empty `Result` if all keys
     /// are successfully deleted, or an `RedisError::DeleteFailed` containing a description of the error if any failure occurs.
     ///
     /// # Parameters
@@ -357,18 +757,128 @@ impl RedisConnectionPool {
     /// }
     /// ```
     pub async fn delete_keys(&self, keys: Vec<&str>) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("delete_keys");
+        self.del_keys(keys).await?;
+        Ok(())
+    }
+
+    /// Deletes multiple keys in the Redis store as a part of a single pipeline, as with `delete_keys`, returning how
+    /// many of them existed.
+    ///
+    /// # Parameters
+    /// - `keys: Vec<&str>` - A vector containing the keys to be deleted from the Redis store.
+    ///
+    /// # Returns
+    /// - `Result<u64, RedisError>` - The number of keys that existed and were deleted, or an `RedisError::DeleteFailed` on failure.
+    pub async fn delete_keys_with_count(&self, keys: Vec<&str>) -> Result<u64, RedisError> {
+        let _timer = redis_operation_timer("delete_keys_with_count");
+        self.del_keys(keys).await
+    }
+
+    async fn del_keys(&self, keys: Vec<&str>) -> Result<u64, RedisError> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
         let pipeline = self.pool.pipeline();
 
         for key in keys {
-            let _ = pipeline.del::<RedisValue, &str>(key).await;
+            let _ = pipeline.del::<RedisValue, _>(self.add_prefix(key)).await;
         }
 
-        pipeline
+        let deleted: Vec<u64> = pipeline
             .all()
             .await
-            .map_err(|err| RedisError::DeleteFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::DeleteFailed))?;
 
-        Ok(())
+        Ok(deleted.into_iter().sum())
+    }
+
+    /// Counts how many of multiple keys exist in the Redis store, as a part of a single pipeline.
+    ///
+    /// Each key is checked with its own command, so the keys do not need to belong to the same hash slot in
+    /// cluster mode. A key given more than once is counted as many times.
+    ///
+    /// # Parameters
+    /// - `keys: Vec<&str>` - A vector containing the keys to be checked.
+    ///
+    /// # Returns
+    /// - `Result<u64, RedisError>` - The number of keys that exist, or an `RedisError::ExistsFailed` on failure.
+    pub async fn exists_keys(&self, keys: Vec<&str>) -> Result<u64, RedisError> {
+        let _timer = redis_operation_timer("exists_keys");
+        if keys.is_empty() {
+            return Ok(0);
+        }
+
+        let pipeline = self.pool.pipeline();
+
+        for key in keys {
+            let _ = pipeline.exists::<RedisValue, _>(self.add_prefix(key)).await;
+        }
+
+        let existing: Vec<u64> = pipeline
+            .all()
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::ExistsFailed))?;
+
+        Ok(existing.into_iter().sum())
+    }
+
+    /// Incrementally iterates over the keys matching a pattern in the Redis store.
+    ///
+    /// This function uses the cursor based `SCAN` command instead of `KEYS`, so the server is never blocked
+    /// while enumerating a large keyspace. Keys are yielded as each page arrives, and the next page is only
+    /// requested once the current one has been handed to the stream. In cluster mode every primary node is scanned.
+    ///
+    /// # Parameters
+    /// - `pattern: &str` - The glob-style pattern the keys should match.
+    /// - `count: Option<u32>` - An optional hint for the number of keys returned per page.
+    ///
+    /// # Returns
+    /// - `impl Stream<Item = Result<String, RedisError>>` - A stream of the matching keys, yielding an
+    ///   `RedisError::ScanFailed` if fetching a page fails.
+    ///
+    /// Note: Keys may be yielded more than once if they are modified while the scan is in progress.
+    pub fn scan_keys(
+        &self,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> impl Stream<Item = Result<String, RedisError>> {
+        let client = self.pool.next();
+        let pattern = self.add_prefix(pattern);
+        let key_prefix = self.key_prefix.to_owned();
+
+        let pages = if client.is_clustered() {
+            client.scan_cluster(pattern, count, None).boxed()
+        } else {
+            client.scan(pattern, count, None).boxed()
+        };
+
+        pages.flat_map(move |page| match page {
+            Ok(mut page) => {
+                let keys = page.take_results().unwrap_or_default();
+                if let Err(err) = page.next() {
+                    error!(%err, "Failed to request the next page of keys");
+                }
+                stream::iter(
+                    keys.into_iter()
+                        .map(|key| {
+                            key.into_string()
+                                .map(|key| strip_key_prefix(&key_prefix, key))
+                                .ok_or_else(|| {
+                                    RedisError::ScanFailed(
+                                        "Unexpected non UTF-8 key encountered".to_string(),
+                                    )
+                                })
+                        })
+                        .collect::<Vec<Result<String, RedisError>>>(),
+                )
+            }
+            Err(err) => stream::iter(vec![Err(RedisError::from_fred(
+                err,
+                RedisError::ScanFailed,
+            ))]),
+        })
     }
 
     /// Sets multiple fields in a hash in the Redis store and applies an expiry time to the hash.
@@ -410,15 +920,52 @@ impl RedisConnectionPool {
         V: TryInto<RedisMap> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
+        let _timer = redis_operation_timer("set_hash_fields");
         self.pool
-            .hset(key, values)
+            .hset(self.add_prefix(key), values)
             .await
-            .map_err(|err| RedisError::SetHashFieldFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::SetHashFieldFailed))?;
 
-        self.set_expiry(key, expiry).await?;
+        self.expire(key, expiry).await?;
         Ok(())
     }
 
+    /// Sets a single field of a hash in the Redis store.
+    ///
+    /// When this write creates the hash, the `default_hash_ttl` (from the `RedisSettings`) is applied to it, so
+    /// repeated writes do not keep extending the lifetime of the hash. The write and the expiry run in a single Lua
+    /// script, so a newly created hash can never be left without an expiry.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key representing the hash in the Redis store.
+    /// - `field: &str` - The field within the hash to be set.
+    /// - `value: &str` - The value to be set, stored as is.
+    ///
+    /// # Returns
+    /// - `Result<bool, RedisError>` - `true` if the field is new, `false` if an existing field was updated (`Ok`),
+    ///   or an `RedisError::EvalFailed` (`Err`) if the script fails.
+    pub async fn set_hash_field(
+        &self,
+        key: &str,
+        field: &str,
+        value: &str,
+    ) -> Result<bool, RedisError> {
+        let _timer = redis_operation_timer("set_hash_field");
+        let added: i64 = self
+            .eval(
+                SET_HASH_FIELD_SCRIPT,
+                vec![key.to_string()],
+                vec![
+                    self.default_hash_ttl.to_string(),
+                    field.to_string(),
+                    value.to_string(),
+                ],
+            )
+            .await?;
+
+        Ok(added == 1)
+    }
+
     /// Retrieves a field value from a hash in the Redis store.
     ///
     /// This asynchronous function receives a key representing a hash and a field within that hash,
@@ -450,16 +997,93 @@ impl RedisConnectionPool {
     where
         V: FromRedis + Unpin + Send + 'static,
     {
+        let _timer = redis_operation_timer("get_hash_field");
+        let key = &self.add_prefix(key);
+
         self.pool
             .hget(key, field)
             .await
-            .map_err(|err| RedisError::GetHashFieldFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::GetHashFieldFailed))
     }
 
-    /// Appends one or multiple values to the end of a list in the Redis store.
+    /// Retrieves all the fields and their values from a hash in the Redis store.
     ///
-    /// This asynchronous function receives a key representing a list and a vector of values to be appended to the list.
-    /// It attempts to append the values to the end of the list and returns the length of the list after the push operation.
+    /// This asynchronous function receives a key representing a hash and returns every field-value pair in it.
+    /// If the hash does not exist, an empty map is returned.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key representing the hash in the Redis store.
+    ///
+    /// # Returns
+    /// - `Result<FxHashMap<String, String>, RedisError>` - A `Result` containing the field-value pairs of the hash on success,
+    ///   or an `RedisError::GetHashFieldFailed` on failure.
+    pub async fn get_all_hash_fields(
+        &self,
+        key: &str,
+    ) -> Result<FxHashMap<String, String>, RedisError> {
+        let _timer = redis_operation_timer("get_all_hash_fields");
+        let key = &self.add_prefix(key);
+
+        self.pool
+            .hgetall(key)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::GetHashFieldFailed))
+    }
+
+    /// Prepends one or multiple values to the start of a list in the Redis store.
+    ///
+    /// The values are inserted one after the other at the head of the list, so the last value ends up first.
+    /// If the vector of values is empty, it will return the current length of the list without modifying it.
+    ///
+    /// # Type Parameters
+    /// - `V` - The type of the values to be prepended to the list. Must implement `Serialize`, `Debug`, `Send`, `Sync`, and `Clone`.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key representing the list in the Redis store.
+    /// - `values: Vec<V>` - A vector of values to be prepended to the start of the list.
+    ///
+    /// # Returns
+    /// - `Result<i64, RedisError>` - A `Result` containing the length of the list after the push operation (`Ok`)
+    ///   or an `RedisError::LPushFailed` (`Err`) if any failure occurs.
+    pub async fn lpush<V>(&self, key: &str, values: Vec<V>) -> Result<i64, RedisError>
+    where
+        V: Serialize + Debug + Send + Sync + Clone,
+    {
+        let _timer = redis_operation_timer("lpush");
+        if values.is_empty() {
+            return self.list_len(key).await;
+        }
+
+        let key = &self.add_prefix(key);
+
+        let serialized_value = values
+            .iter()
+            .map(|value| {
+                serde_json::to_string(value)
+                    .map(Into::into)
+                    .map_err(|err| RedisError::SerializationError(err.to_string()))
+            })
+            .collect::<Result<Vec<RedisValue>, RedisError>>()?;
+
+        let output = self
+            .pool
+            .lpush(key, serialized_value)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::LPushFailed))?;
+
+        match output {
+            RedisValue::Integer(length) => Ok(length),
+            case => Err(RedisError::LPushFailed(format!(
+                "Unexpected RedisValue encountered : {:?}",
+                case
+            ))),
+        }
+    }
+
+    /// Appends one or multiple values to the end of a list in the Redis store.
+    ///
+    /// This asynchronous function receives a key representing a list and a vector of values to be appended to the list.
+    /// It attempts to append the values to the end of the list and returns the length of the list after the push operation.
     /// If the vector of values is empty, it will return the current length of the list without modifying it.
     ///
     /// # Type Parameters
@@ -486,10 +1110,13 @@ impl RedisConnectionPool {
     where
         V: Serialize + Debug + Send + Sync + Clone,
     {
+        let _timer = redis_operation_timer("rpush");
         if values.is_empty() {
-            return self.llen(key).await;
+            return self.list_len(key).await;
         }
 
+        let key = &self.add_prefix(key);
+
         let serialized_value = values
             .iter()
             .map(|value| {
@@ -503,7 +1130,7 @@ impl RedisConnectionPool {
             .pool
             .rpush(key, serialized_value)
             .await
-            .map_err(|err| RedisError::RPushFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::RPushFailed))?;
 
         match output {
             RedisValue::Integer(length) => Ok(length),
@@ -551,10 +1178,12 @@ impl RedisConnectionPool {
     where
         V: Serialize + Debug + Send + Sync + Clone,
     {
+        let _timer = redis_operation_timer("rpush_with_expiry");
         if values.is_empty() {
-            return self.llen(key).await;
+            return self.list_len(key).await;
         }
 
+        let key = &self.add_prefix(key);
         let pipeline = self.pool.pipeline();
 
         let serialized_value = values
@@ -574,7 +1203,7 @@ impl RedisConnectionPool {
         let output: Vec<RedisValue> = pipeline
             .all()
             .await
-            .map_err(|err| RedisError::RPushFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::RPushFailed))?;
 
         match output.deref() {
             [RedisValue::Integer(length), ..] => Ok(length.to_owned()),
@@ -611,11 +1240,14 @@ impl RedisConnectionPool {
     where
         T: DeserializeOwned,
     {
+        let _timer = redis_operation_timer("rpop");
+        let key = &self.add_prefix(key);
+
         let output = self
             .pool
             .rpop(key, count)
             .await
-            .map_err(|err| RedisError::RPopFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::RPopFailed))?;
 
         match output {
             RedisValue::Array(val) => {
@@ -673,11 +1305,14 @@ impl RedisConnectionPool {
     where
         T: DeserializeOwned,
     {
+        let _timer = redis_operation_timer("lpop");
+        let key = &self.add_prefix(key);
+
         let output = self
             .pool
             .lpop(key, count)
             .await
-            .map_err(|err| RedisError::LPopFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::LPopFailed))?;
 
         match output {
             RedisValue::Array(val) => {
@@ -734,11 +1369,14 @@ impl RedisConnectionPool {
     ///
     /// Note: This function will return an empty vector if the specified range does not contain any elements.
     pub async fn lrange(&self, key: &str, min: i64, max: i64) -> Result<Vec<String>, RedisError> {
+        let _timer = redis_operation_timer("lrange");
+        let key = &self.add_prefix(key);
+
         let output = self
             .pool
             .lrange(key, min, max)
             .await
-            .map_err(|err| RedisError::LRangeFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::LRangeFailed))?;
 
         match output {
             RedisValue::Array(val) => {
@@ -782,11 +1420,18 @@ impl RedisConnectionPool {
     ///
     /// Note: This function will return 0 if the list does not exist.
     pub async fn llen(&self, key: &str) -> Result<i64, RedisError> {
+        let _timer = redis_operation_timer("llen");
+        self.list_len(key).await
+    }
+
+    async fn list_len(&self, key: &str) -> Result<i64, RedisError> {
+        let key = &self.add_prefix(key);
+
         let output = self
             .pool
             .llen(key)
             .await
-            .map_err(|err| RedisError::LLenFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::LLenFailed))?;
 
         match output {
             RedisValue::Integer(length) => Ok(length),
@@ -843,10 +1488,120 @@ impl RedisConnectionPool {
     where
         V: Into<MultipleGeoValues> + Send + Debug,
     {
+        let _timer = redis_operation_timer("geo_add");
+        self.geoadd(key, values, options, changed).await
+    }
+
+    async fn geoadd<V>(
+        &self,
+        key: &str,
+        values: V,
+        options: Option<SetOptions>,
+        changed: bool,
+    ) -> Result<(), RedisError>
+    where
+        V: Into<MultipleGeoValues> + Send + Debug,
+    {
+        let key = &self.add_prefix(key);
+
         self.pool
             .geoadd(key, options, changed, values)
             .await
-            .map_err(|err| RedisError::GeoAddFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::GeoAddFailed))
+    }
+
+    /// Adds a single member at the given point to the specified key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice that holds the name of the key to which the member is added.
+    /// * `member` - The name of the member to add.
+    /// * `point` - The location of the member.
+    ///
+    /// # Returns
+    ///
+    /// If successful, the function returns `Ok(())`, indicating that the member was added.
+    /// If an error occurs, it returns an `Err(RedisError)` variant indicating the type of error.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `Err` variant of `RedisError` with `InvalidInput` if the point is out of
+    /// the range Redis can index, or with `GeoAddFailed` if the Redis operation fails.
+    pub async fn geo_add_point(
+        &self,
+        key: &str,
+        member: &str,
+        point: &Point,
+    ) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("geo_add_point");
+        point.validate()?;
+
+        self.geoadd(
+            key,
+            GeoValue {
+                coordinates: GeoPosition {
+                    longitude: point.lon,
+                    latitude: point.lat,
+                },
+                member: member.into(),
+            },
+            None,
+            false,
+        )
+        .await
+    }
+
+    /// Adds many members at their points to the specified key with a single `GEOADD`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice that holds the name of the key to which the members are added.
+    /// * `members` - The names of the members to add, along with their locations.
+    ///
+    /// # Returns
+    ///
+    /// If successful, the function returns `Ok(u64)` with the number of members newly added, not including the members
+    /// whose location was only updated. If an error occurs, it returns an `Err(RedisError)` variant indicating the type of error.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `Err` variant of `RedisError` with `InvalidInput` naming the first member whose point
+    /// is out of the range Redis can index, in which case nothing is sent, or with `GeoAddFailed` if the Redis operation fails.
+    pub async fn geo_add_batch(
+        &self,
+        key: &str,
+        members: &[(String, Point)],
+    ) -> Result<u64, RedisError> {
+        let _timer = redis_operation_timer("geo_add_batch");
+        if members.is_empty() {
+            return Ok(0);
+        }
+
+        let values = members
+            .iter()
+            .map(|(member, point)| {
+                point.validate().map_err(|_| {
+                    RedisError::InvalidInput(format!(
+                        "Invalid coordinates for member {} : lat {}, lon {}",
+                        member, point.lat, point.lon
+                    ))
+                })?;
+                Ok(GeoValue {
+                    coordinates: GeoPosition {
+                        longitude: point.lon,
+                        latitude: point.lat,
+                    },
+                    member: member.as_str().into(),
+                })
+            })
+            .collect::<Result<Vec<GeoValue>, RedisError>>()?;
+
+        let key = &self.add_prefix(key);
+
+        self.pool
+            .geoadd(key, None, false, values)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::GeoAddFailed))
     }
 
     /// Adds geospatial items to the specified key with an expiry time.
@@ -880,6 +1635,9 @@ impl RedisConnectionPool {
     where
         V: Into<MultipleGeoValues> + Send + Debug,
     {
+        let _timer = redis_operation_timer("geo_add_with_expiry");
+        let key = &self.add_prefix(key);
+
         let pipeline = self.pool.pipeline();
 
         let _ = pipeline
@@ -890,7 +1648,7 @@ impl RedisConnectionPool {
         pipeline
             .all()
             .await
-            .map_err(|err| RedisError::GeoAddFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::GeoAddFailed))
     }
 
     /// Adds multiple geospatial items with an expiry to various keys in a transactional way.
@@ -933,9 +1691,11 @@ impl RedisConnectionPool {
         changed: bool,
         expiry: i64,
     ) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("mgeo_add_with_expiry");
         let pipeline = self.pool.pipeline();
 
         for (key, values) in mval.iter() {
+            let key = &self.add_prefix(key);
             let _ = pipeline
                 .geoadd::<RedisValue, &str, MultipleGeoValues>(
                     key,
@@ -950,7 +1710,7 @@ impl RedisConnectionPool {
         pipeline
             .all()
             .await
-            .map_err(|err| RedisError::GeoAddFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::GeoAddFailed))
     }
 
     /// Performs a search on a geospatial index to find items within a specified area.
@@ -996,6 +1756,9 @@ impl RedisConnectionPool {
         by_radius: (f64, GeoUnit),
         ord: SortOrder,
     ) -> Result<Vec<GeoRadiusInfo>, RedisError> {
+        let _timer = redis_operation_timer("geo_search");
+        let key = &self.add_prefix(key);
+
         self.pool
             .geosearch(
                 key,
@@ -1010,7 +1773,83 @@ impl RedisConnectionPool {
                 false,
             )
             .await
-            .map_err(|err| RedisError::GeoSearchFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::GeoSearchFailed))
+    }
+
+    /// Finds the members of a geospatial index within a radius around a point.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the geospatial index to search.
+    /// * `center` - The point around which to search.
+    /// * `radius_m` - The radius of the search area, in meters.
+    /// * `count` - An optional limit on the number of members returned.
+    /// * `ord` - An optional `SortOrder` to sort the results by distance.
+    ///
+    /// # Returns
+    ///
+    /// If successful, the function returns `Ok(Vec<(String, Point, f64)>)` containing the name, location and
+    /// distance from the center in meters of each member found. On failure, it returns an `Err(RedisError)`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an `Err` variant of `RedisError` with `InvalidInput` if the center is out of
+    /// the range Redis can index, or with `GeoSearchFailed` if the Redis operation fails or returns an unexpected value.
+    pub async fn geo_search_points(
+        &self,
+        key: &str,
+        center: &Point,
+        radius_m: f64,
+        count: Option<u64>,
+        ord: Option<SortOrder>,
+    ) -> Result<Vec<(String, Point, f64)>, RedisError> {
+        let _timer = redis_operation_timer("geo_search_points");
+        let key = &self.add_prefix(key);
+
+        center.validate()?;
+
+        let output = self
+            .pool
+            .geosearch(
+                key,
+                None,
+                Some(GeoPosition {
+                    longitude: center.lon,
+                    latitude: center.lat,
+                }),
+                Some((radius_m, GeoUnit::Meters)),
+                None,
+                ord,
+                count.map(|count| (count, false)),
+                true,
+                true,
+                false,
+            )
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::GeoSearchFailed))?;
+
+        output
+            .into_iter()
+            .map(|info| match info {
+                GeoRadiusInfo {
+                    member: RedisValue::String(member),
+                    position: Some(position),
+                    distance: Some(distance),
+                    ..
+                } => Ok((
+                    member.to_string(),
+                    Point {
+                        lat: position.latitude,
+                        lon: position.longitude,
+                    },
+                    distance,
+                )),
+                case => Err(RedisError::GeoSearchFailed(format!(
+                    "Unexpected RedisValue encountered : {:?}",
+                    case
+                ))),
+            })
+            .collect()
     }
 
     /// Performs a geographical search on multiple Redis keys to find members within a specified area.
@@ -1038,12 +1877,13 @@ impl RedisConnectionPool {
         by_radius: (f64, GeoUnit),
         ord: SortOrder,
     ) -> Result<Vec<Option<(String, Point)>>, RedisError> {
+        let _timer = redis_operation_timer("mgeo_search");
         let pipeline = self.pool.pipeline();
 
         for key in keys {
             let _ = pipeline
                 .geosearch(
-                    key,
+                    self.add_prefix(&key),
                     None,
                     Some(from_lonlat.to_owned()),
                     Some(by_radius.to_owned()),
@@ -1060,7 +1900,7 @@ impl RedisConnectionPool {
         let geovals: Vec<Option<(String, Point)>> = pipeline
             .all::<Vec<Vec<RedisValue>>>()
             .await
-            .map_err(|err| RedisError::GeoSearchFailed(err.to_string()))?
+            .map_err(|err| RedisError::from_fred(err, RedisError::GeoSearchFailed))?
             .into_iter()
             .map(|geoval| {
                 if let [RedisValue::String(member), RedisValue::Array(position)] = &geoval[..] {
@@ -1089,11 +1929,14 @@ impl RedisConnectionPool {
     }
 
     pub async fn geopos(&self, key: &str, members: Vec<String>) -> Result<Vec<Point>, RedisError> {
+        let _timer = redis_operation_timer("geopos");
+        let key = &self.add_prefix(key);
+
         let output = self
             .pool
             .geopos(key, members)
             .await
-            .map_err(|err| RedisError::GeoPosFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::GeoPosFailed))?;
 
         match output {
             RedisValue::Array(points) => {
@@ -1157,10 +2000,13 @@ impl RedisConnectionPool {
         start: i64,
         stop: i64,
     ) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("zremrange_by_rank");
+        let key = &self.add_prefix(key);
+
         self.pool
             .zremrangebyrank(key, start, stop)
             .await
-            .map_err(|err| RedisError::ZremrangeByRankFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::ZremrangeByRankFailed))
     }
 
     /// Asynchronously adds one or multiple members to a sorted set, or updates its score if it already exists.
@@ -1197,10 +2043,13 @@ impl RedisConnectionPool {
         incr: bool,
         values: Vec<(f64, &str)>,
     ) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("zadd");
+        let key = &self.add_prefix(key);
+
         self.pool
             .zadd(key, options, ordering, changed, incr, values)
             .await
-            .map_err(|err| RedisError::ZAddFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::ZAddFailed))
     }
 
     /// Asynchronously retrieves the number of elements in a sorted set stored at the specified key.
@@ -1225,10 +2074,13 @@ impl RedisConnectionPool {
     /// println!("Number of members in sorted set: {}", count);
     /// ```
     pub async fn zcard(&self, key: &str) -> Result<u64, RedisError> {
+        let _timer = redis_operation_timer("zcard");
+        let key = &self.add_prefix(key);
+
         self.pool
             .zcard(key)
             .await
-            .map_err(|err| RedisError::ZCardFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::ZCardFailed))
     }
 
     /// Asynchronously retrieves a range of elements from a sorted set stored at the specified key.
@@ -1274,11 +2126,14 @@ impl RedisConnectionPool {
     where
         T: DeserializeOwned,
     {
+        let _timer = redis_operation_timer("zrange");
+        let key = &self.add_prefix(key);
+
         let output = self
             .pool
             .zrange(key, min, max, sort, rev, limit, withscores)
             .await
-            .map_err(|err| RedisError::ZRangeFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::ZRangeFailed))?;
 
         match output {
             RedisValue::Array(val) => {
@@ -1305,6 +2160,110 @@ impl RedisConnectionPool {
         }
     }
 
+    /// Asynchronously retrieves the members of a sorted set along with their scores, ordered from the lowest to the highest score.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the Redis sorted set.
+    /// * `start` - The starting rank (index) of the range, negative values count from the end.
+    /// * `stop` - The stopping rank (index) of the range, inclusive, negative values count from the end.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(String, f64)>`: The members and their scores, in order.
+    /// * `RedisError`: An error variant indicating a problem interfacing with Redis.
+    pub async fn zrange_with_scores(
+        &self,
+        key: &str,
+        start: i64,
+        stop: i64,
+    ) -> Result<Vec<(String, f64)>, RedisError> {
+        let _timer = redis_operation_timer("zrange_with_scores");
+        self.zrange_scores(key, start, stop, false).await
+    }
+
+    /// Asynchronously retrieves the members of a sorted set along with their scores, ordered from the highest to the lowest score.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the Redis sorted set.
+    /// * `start` - The starting rank (index) of the range in the reversed order, negative values count from the end.
+    /// * `stop` - The stopping rank (index) of the range in the reversed order, inclusive, negative values count from the end.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<(String, f64)>`: The members and their scores, in order.
+    /// * `RedisError`: An error variant indicating a problem interfacing with Redis.
+    pub async fn zrevrange_with_scores(
+        &self,
+        key: &str,
+        start: i64,
+        stop: i64,
+    ) -> Result<Vec<(String, f64)>, RedisError> {
+        let _timer = redis_operation_timer("zrevrange_with_scores");
+        self.zrange_scores(key, start, stop, true).await
+    }
+
+    async fn zrange_scores(
+        &self,
+        key: &str,
+        start: i64,
+        stop: i64,
+        rev: bool,
+    ) -> Result<Vec<(String, f64)>, RedisError> {
+        let key = &self.add_prefix(key);
+
+        let output: RedisValue = self
+            .pool
+            .zrange(key, start, stop, None, rev, None, true)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::ZRangeFailed))?;
+
+        parse_scored_members(output)
+    }
+
+    /// Asynchronously retrieves the rank of a member in a sorted set, with the scores ordered from the lowest to the highest.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the Redis sorted set.
+    /// * `member` - The member whose rank is retrieved.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<u64>`: The zero based rank of the member, or `None` if the member or the sorted set does not exist.
+    /// * `RedisError`: An error variant indicating a problem interfacing with Redis.
+    pub async fn zrank(&self, key: &str, member: &str) -> Result<Option<u64>, RedisError> {
+        let _timer = redis_operation_timer("zrank");
+        let key = &self.add_prefix(key);
+
+        self.pool
+            .zrank(key, member)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::ZRankFailed))
+    }
+
+    /// Asynchronously retrieves the score of a member in a sorted set.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the Redis sorted set.
+    /// * `member` - The member whose score is retrieved.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>`: The score of the member, or `None` if the member or the sorted set does not exist.
+    /// * `RedisError`: An error variant indicating a problem interfacing with Redis.
+    pub async fn zscore(&self, key: &str, member: &str) -> Result<Option<f64>, RedisError> {
+        let _timer = redis_operation_timer("zscore");
+        let key = &self.add_prefix(key);
+
+        self.pool
+            .zscore(key, member)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::ZScoreFailed))
+    }
+
     pub async fn xadd<F, V>(
         &self,
         key: &str,
@@ -1315,6 +2274,9 @@ impl RedisConnectionPool {
         F: Into<RedisKey> + Send,
         V: Into<RedisValue> + Send,
     {
+        let _timer = redis_operation_timer("xadd");
+        let key = &self.add_prefix(key);
+
         self.pool
             .xadd(
                 key,
@@ -1329,26 +2291,277 @@ impl RedisConnectionPool {
                 fields,
             )
             .await
-            .map_err(|err| RedisError::XAddFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::XAddFailed))?;
 
         Ok(())
     }
 
-    pub async fn xread(
+    /// Appends an entry holding a JSON serialized payload to a stream.
+    ///
+    /// The payload is stored under a single field, `STREAM_PAYLOAD_FIELD`, and the stream is optionally capped
+    /// to approximately `maxlen` entries (`MAXLEN ~`).
+    ///
+    /// # Type Parameters
+    /// - `T` - The type of the payload. Must implement `Serialize`.
+    ///
+    /// # Parameters
+    /// - `stream: &str` - The key of the stream.
+    /// - `payload: &T` - The payload of the entry.
+    /// - `maxlen: Option<usize>` - The approximate maximum length of the stream, if it should be trimmed.
+    ///
+    /// # Returns
+    /// - `Result<String, RedisError>` - The id generated for the entry (`Ok`), or an `RedisError::XAddFailed` (`Err`) if the operation fails.
+    pub async fn stream_add<T>(
         &self,
-        keys: Vec<String>,
-        ids: Vec<String>,
-    ) -> Result<FxHashMap<String, Vec<Vec<(String, String)>>>, RedisError> {
+        stream: &str,
+        payload: &T,
+        maxlen: Option<usize>,
+    ) -> Result<String, RedisError>
+    where
+        T: Serialize,
+    {
+        let _timer = redis_operation_timer("stream_add");
+        let stream = &self.add_prefix(stream);
+
+        let serialized_payload = serde_json::to_string(payload)
+            .map_err(|err| RedisError::SerializationError(err.to_string()))?;
+
+        let cap = match maxlen {
+            Some(maxlen) => XCap::try_from((
+                XCapKind::MaxLen,
+                XCapTrim::AlmostExact,
+                StringOrNumber::Number(maxlen as i64),
+            ))
+            .map_err(|err| RedisError::from_fred(err, RedisError::XAddFailed))?,
+            None => XCap::from(None::<()>),
+        };
+
+        self.pool
+            .xadd(
+                stream,
+                false,
+                cap,
+                Auto,
+                vec![(STREAM_PAYLOAD_FIELD, serialized_payload)],
+            )
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::XAddFailed))
+    }
+
+    /// Reads the entries written with `stream_add` after the given id from a stream.
+    ///
+    /// At most `stream_read_count` (from the `RedisSettings`) entries are read per call.
+    ///
+    /// # Type Parameters
+    /// - `T` - The type of the payload. Must implement `DeserializeOwned`.
+    ///
+    /// # Parameters
+    /// - `stream: &str` - The key of the stream.
+    /// - `last_id: &str` - The id after which the entries are read, e.g. `0` to read from the start of the stream.
+    ///
+    /// # Returns
+    /// - `Result<Vec<(String, T)>, RedisError>` - The ids and the deserialized payloads of the entries, in order (`Ok`).
+    ///   The last id can be used as `last_id` for the next read. Returns an `RedisError::XReadFailed` if the operation fails,
+    ///   or an `RedisError::DeserializationError` if an entry has no valid payload.
+    pub async fn stream_read<T>(
+        &self,
+        stream: &str,
+        last_id: &str,
+    ) -> Result<Vec<(String, T)>, RedisError>
+    where
+        T: DeserializeOwned,
+    {
+        let _timer = redis_operation_timer("stream_read");
+        let stream = &self.add_prefix(stream);
+
         let output: RedisValue = self
             .pool
             .xread(
+                Some(self.stream_read_count),
                 None,
-                None,
-                keys,
-                ids.iter().map(|id| Manual(id.into())).collect::<Vec<XID>>(),
+                stream,
+                Manual(last_id.into()),
             )
             .await
-            .map_err(|err| RedisError::XReadFailed(err.to_string()))?;
+            .map_err(|err| RedisError::from_fred(err, RedisError::XReadFailed))?;
+
+        // Only `XREADGROUP` returns entries without fields, so every entry read here has a payload.
+        parse_stream_entries(stream, output, RedisError::XReadFailed).map(|entries| {
+            entries
+                .into_iter()
+                .filter_map(|(id, payload)| payload.map(|payload| (id, payload)))
+                .collect()
+        })
+    }
+
+    /// Creates a consumer group on a stream, creating the stream if it does not exist.
+    ///
+    /// # Parameters
+    /// - `stream: &str` - The key of the stream.
+    /// - `group: &str` - The name of the consumer group.
+    /// - `start_id: &str` - The id after which the entries are delivered to the group, e.g. `$` for new entries only
+    ///   or `0` for the whole stream.
+    ///
+    /// # Returns
+    /// - `Result<bool, RedisError>` - `true` if the group was created, `false` if it already exists (`Ok`), or an
+    ///   `RedisError::XGroupCreateFailed` (`Err`) if the operation fails.
+    pub async fn stream_create_group(
+        &self,
+        stream: &str,
+        group: &str,
+        start_id: &str,
+    ) -> Result<bool, RedisError> {
+        let _timer = redis_operation_timer("stream_create_group");
+        let stream = &self.add_prefix(stream);
+
+        match self
+            .pool
+            .xgroup_create::<RedisValue, _, _, _>(stream, group, start_id, true)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(err) if err.details().starts_with("BUSYGROUP") => Ok(false),
+            Err(err) => Err(RedisError::from_fred(err, RedisError::XGroupCreateFailed)),
+        }
+    }
+
+    /// Reads the entries written with `stream_add` from a stream as a consumer of a group.
+    ///
+    /// At most `stream_read_count` (from the `RedisSettings`) entries are read per call. The entries read stay pending
+    /// for the consumer until they are acknowledged with `stream_ack`.
+    ///
+    /// # Type Parameters
+    /// - `T` - The type of the payload. Must implement `DeserializeOwned`.
+    ///
+    /// # Parameters
+    /// - `stream: &str` - The key of the stream.
+    /// - `group: &str` - The name of the consumer group.
+    /// - `consumer: &str` - The name of the consumer within the group.
+    /// - `pending: bool` - Whether to read the entries already delivered to this consumer but not yet acknowledged,
+    ///   e.g. to resume after a restart, instead of new entries.
+    ///
+    /// # Returns
+    /// - `Result<Vec<(String, Option<T>)>, RedisError>` - The ids and the deserialized payloads of the entries, in order
+    ///   (`Ok`). When reading pending entries, the payload is `None` for the entries trimmed or deleted from the stream
+    ///   since they were delivered, which can then only be acknowledged. Returns an `RedisError::XReadGroupFailed` if
+    ///   the operation fails, or an `RedisError::DeserializationError` if an entry has no valid payload.
+    pub async fn stream_read_group<T>(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        pending: bool,
+    ) -> Result<Vec<(String, Option<T>)>, RedisError>
+    where
+        T: DeserializeOwned,
+    {
+        let _timer = redis_operation_timer("stream_read_group");
+        let stream = &self.add_prefix(stream);
+
+        let id = if pending {
+            Manual("0".into())
+        } else {
+            XID::NewInGroup
+        };
+
+        let output: RedisValue = self
+            .pool
+            .xreadgroup(
+                group,
+                consumer,
+                Some(self.stream_read_count),
+                None,
+                false,
+                stream,
+                id,
+            )
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::XReadGroupFailed))?;
+
+        parse_stream_entries(stream, output, RedisError::XReadGroupFailed)
+    }
+
+    /// Acknowledges entries of a stream processed by a consumer of a group, removing them from its pending entries.
+    ///
+    /// # Parameters
+    /// - `stream: &str` - The key of the stream.
+    /// - `group: &str` - The name of the consumer group.
+    /// - `ids: Vec<String>` - The ids of the entries to acknowledge.
+    ///
+    /// # Returns
+    /// - `Result<u64, RedisError>` - The number of entries acknowledged (`Ok`), or an `RedisError::XAckFailed` (`Err`)
+    ///   if the operation fails.
+    pub async fn stream_ack(
+        &self,
+        stream: &str,
+        group: &str,
+        ids: Vec<String>,
+    ) -> Result<u64, RedisError> {
+        let _timer = redis_operation_timer("stream_ack");
+        let stream = &self.add_prefix(stream);
+
+        self.pool
+            .xack(stream, group, ids)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::XAckFailed))
+    }
+
+    /// Lists the entries of a stream delivered to the consumers of a group but not yet acknowledged.
+    ///
+    /// At most `stream_read_count` (from the `RedisSettings`) entries are listed, oldest first.
+    ///
+    /// # Parameters
+    /// - `stream: &str` - The key of the stream.
+    /// - `group: &str` - The name of the consumer group.
+    ///
+    /// # Returns
+    /// - `Result<Vec<PendingEntry>, RedisError>` - The pending entries (`Ok`), or an `RedisError::XPendingFailed` (`Err`)
+    ///   if the operation fails.
+    pub async fn stream_pending(
+        &self,
+        stream: &str,
+        group: &str,
+    ) -> Result<Vec<PendingEntry>, RedisError> {
+        let _timer = redis_operation_timer("stream_pending");
+        let stream = &self.add_prefix(stream);
+
+        let output: Vec<(String, String, u64, u64)> = self
+            .pool
+            .xpending(stream, group, ("-", "+", self.stream_read_count))
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::XPendingFailed))?;
+
+        Ok(output
+            .into_iter()
+            .map(
+                |(id, consumer, idle_time_ms, delivery_count)| PendingEntry {
+                    id,
+                    consumer,
+                    idle_time_ms,
+                    delivery_count,
+                },
+            )
+            .collect())
+    }
+
+    pub async fn xread(
+        &self,
+        keys: Vec<String>,
+        ids: Vec<String>,
+    ) -> Result<FxHashMap<String, Vec<Vec<(String, String)>>>, RedisError> {
+        let _timer = redis_operation_timer("xread");
+        let output: RedisValue = self
+            .pool
+            .xread(
+                None,
+                None,
+                keys.iter()
+                    .map(|key| self.add_prefix(key))
+                    .collect::<Vec<String>>(),
+                ids.iter().map(|id| Manual(id.into())).collect::<Vec<XID>>(),
+            )
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::XReadFailed))?;
 
         let mut result = FxHashMap::default();
 
@@ -1357,7 +2570,8 @@ impl RedisConnectionPool {
                 for (redis_key, value_array) in output.inner() {
                     if let RedisValue::Array(value_array) = value_array {
                         // Convert RedisKey to String key
-                        let key = redis_key.into_string().unwrap();
+                        let key =
+                            strip_key_prefix(&self.key_prefix, redis_key.into_string().unwrap());
 
                         let mut entries = Vec::new();
 
@@ -1403,9 +2617,750 @@ impl RedisConnectionPool {
     }
 
     pub async fn xdel(&self, key: &str, id: &str) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("xdel");
+        let key = &self.add_prefix(key);
+
         self.pool
             .xdel(key, id)
             .await
-            .map_err(|err| RedisError::XDeleteFailed(err.to_string()))
+            .map_err(|err| RedisError::from_fred(err, RedisError::XDeleteFailed))
+    }
+
+    /// Adds elements to a HyperLogLog in the Redis store, used to approximate the number of unique elements.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key of the HyperLogLog, created if it does not exist.
+    /// - `elements: Vec<String>` - The elements to add.
+    ///
+    /// # Returns
+    /// - `Result<bool, RedisError>` - `true` if the approximated cardinality changed (`Ok`), or an
+    ///   `RedisError::PfAddFailed` (`Err`) if the operation fails.
+    pub async fn pfadd(&self, key: &str, elements: Vec<String>) -> Result<bool, RedisError> {
+        let _timer = redis_operation_timer("pfadd");
+        let key = &self.add_prefix(key);
+
+        self.pool
+            .pfadd(key, elements)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::PfAddFailed))
+    }
+
+    /// Approximates the number of unique elements added to one or multiple HyperLogLogs in the Redis store.
+    ///
+    /// # Parameters
+    /// - `keys: Vec<String>` - The keys of the HyperLogLogs. With multiple keys the cardinality of their union is returned.
+    ///
+    /// # Returns
+    /// - `Result<u64, RedisError>` - The approximated cardinality, `0` if none of the keys exist (`Ok`), or an
+    ///   `RedisError::PfCountFailed` (`Err`) if the operation fails.
+    ///
+    /// Note: In cluster mode all the keys must belong to the same hash slot.
+    pub async fn pfcount(&self, keys: Vec<String>) -> Result<u64, RedisError> {
+        let _timer = redis_operation_timer("pfcount");
+        let keys = keys
+            .iter()
+            .map(|key| self.add_prefix(key))
+            .collect::<Vec<String>>();
+
+        self.pool
+            .pfcount(keys)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::PfCountFailed))
+    }
+
+    /// Merges multiple HyperLogLogs in the Redis store into a destination HyperLogLog.
+    ///
+    /// # Parameters
+    /// - `destination: &str` - The key of the HyperLogLog holding the union, created if it does not exist.
+    /// - `sources: Vec<String>` - The keys of the HyperLogLogs to merge.
+    ///
+    /// # Returns
+    /// - `Result<(), RedisError>` - `Ok(())` if the HyperLogLogs were merged, or an `RedisError::PfMergeFailed` (`Err`)
+    ///   if the operation fails.
+    ///
+    /// Note: In cluster mode all the keys, including the destination, must belong to the same hash slot.
+    pub async fn pfmerge(&self, destination: &str, sources: Vec<String>) -> Result<(), RedisError> {
+        let _timer = redis_operation_timer("pfmerge");
+        let destination = &self.add_prefix(destination);
+        let sources = sources
+            .iter()
+            .map(|key| self.add_prefix(key))
+            .collect::<Vec<String>>();
+
+        self.pool
+            .pfmerge(destination, sources)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::PfMergeFailed))
+    }
+
+    /// Publishes a message to a channel in the Redis store.
+    ///
+    /// This asynchronous function serializes the message to JSON and publishes it on the given channel,
+    /// so that it can be consumed by subscribers deserializing the same type.
+    ///
+    /// # Type Parameters
+    /// - `T` - The type of the message to be published. Must implement `Serialize`.
+    ///
+    /// # Parameters
+    /// - `channel: &str` - The channel on which the message is published.
+    /// - `message: &T` - The message to be published.
+    ///
+    /// # Returns
+    /// - `Result<u64, RedisError>` - A `Result` containing the number of clients that received the message (`Ok`),
+    ///   or an `RedisError::PublishError` (`Err`) with a description if any failure occurs.
+    pub async fn publish<T>(&self, channel: &str, message: &T) -> Result<u64, RedisError>
+    where
+        T: Serialize,
+    {
+        let _timer = redis_operation_timer("publish");
+        let serialized_message = serde_json::to_string(message)
+            .map_err(|err| RedisError::SerializationError(err.to_string()))?;
+
+        self.publish_message(channel, &serialized_message).await
+    }
+
+    /// Publishes an already serialized message to a channel in the Redis store.
+    ///
+    /// # Parameters
+    /// - `channel: &str` - The channel on which the message is published.
+    /// - `message: &str` - The message to be published as is.
+    ///
+    /// # Returns
+    /// - `Result<u64, RedisError>` - A `Result` containing the number of clients that received the message (`Ok`),
+    ///   or an `RedisError::PublishError` (`Err`) with a description if any failure occurs.
+    pub async fn publish_str(&self, channel: &str, message: &str) -> Result<u64, RedisError> {
+        let _timer = redis_operation_timer("publish_str");
+        self.publish_message(channel, message).await
+    }
+
+    async fn publish_message(&self, channel: &str, message: &str) -> Result<u64, RedisError> {
+        let channel = &self.add_prefix(channel);
+
+        self.pool
+            .publish(channel, message)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::PublishError))
+    }
+
+    /// Publishes a message to a shard channel in the Redis store (`SPUBLISH`).
+    ///
+    /// In cluster mode the message is only propagated within the shard owning the slot of the channel, instead of
+    /// to every node of the cluster. The message is serialized to JSON as with `publish`.
+    ///
+    /// Note: Sharded pub/sub requires Redis 7 or above.
+    ///
+    /// # Type Parameters
+    /// - `T` - The type of the message to be published. Must implement `Serialize`.
+    ///
+    /// # Parameters
+    /// - `channel: &str` - The shard channel on which the message is published.
+    /// - `message: &T` - The message to be published.
+    ///
+    /// # Returns
+    /// - `Result<u64, RedisError>` - A `Result` containing the number of clients that received the message (`Ok`),
+    ///   or an `RedisError::PublishError` (`Err`) with a description if any failure occurs.
+    pub async fn spublish<T>(&self, channel: &str, message: &T) -> Result<u64, RedisError>
+    where
+        T: Serialize,
+    {
+        let _timer = redis_operation_timer("spublish");
+        let serialized_message = serde_json::to_string(message)
+            .map_err(|err| RedisError::SerializationError(err.to_string()))?;
+
+        self.spublish_message(channel, &serialized_message).await
+    }
+
+    /// Publishes an already serialized message to a shard channel in the Redis store (`SPUBLISH`).
+    ///
+    /// Note: Sharded pub/sub requires Redis 7 or above.
+    ///
+    /// # Parameters
+    /// - `channel: &str` - The shard channel on which the message is published.
+    /// - `message: &str` - The message to be published as is.
+    ///
+    /// # Returns
+    /// - `Result<u64, RedisError>` - A `Result` containing the number of clients that received the message (`Ok`),
+    ///   or an `RedisError::PublishError` (`Err`) with a description if any failure occurs.
+    pub async fn spublish_str(&self, channel: &str, message: &str) -> Result<u64, RedisError> {
+        let _timer = redis_operation_timer("spublish_str");
+        self.spublish_message(channel, message).await
+    }
+
+    async fn spublish_message(&self, channel: &str, message: &str) -> Result<u64, RedisError> {
+        let channel = &self.add_prefix(channel);
+
+        self.pool
+            .spublish(channel, message)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::PublishError))
+    }
+
+    /// Evaluates a Lua script on the Redis server and deserializes its reply.
+    ///
+    /// The script is first run through `EVALSHA` using its SHA1 digest, so that it is not sent over the wire on every call.
+    /// If the server does not have the script cached yet (`NOSCRIPT`), it falls back to `EVAL`, which also caches it for later calls.
+    ///
+    /// # Type Parameters
+    /// - `T` - The type the reply of the script is deserialized into. Must implement `DeserializeOwned`.
+    ///
+    /// # Parameters
+    /// - `script: &str` - The Lua script to be evaluated.
+    /// - `keys: Vec<String>` - The keys accessed by the script, available as `KEYS` in the script.
+    /// - `args: Vec<String>` - The arguments to the script, available as `ARGV` in the script.
+    ///
+    /// # Returns
+    /// - `Result<T, RedisError>` - A `Result` containing the deserialized reply of the script (`Ok`),
+    ///   or an `RedisError::EvalFailed` (`Err`) with a description if the script fails.
+    pub async fn eval_script<T>(
+        &self,
+        script: &str,
+        keys: Vec<String>,
+        args: Vec<String>,
+    ) -> Result<T, RedisError>
+    where
+        T: DeserializeOwned,
+    {
+        let _timer = redis_operation_timer("eval_script");
+        self.eval(script, keys, args).await
+    }
+
+    async fn eval<T>(
+        &self,
+        script: &str,
+        keys: Vec<String>,
+        args: Vec<String>,
+    ) -> Result<T, RedisError>
+    where
+        T: DeserializeOwned,
+    {
+        let hash = fred::util::sha1_hash(script);
+        let keys = keys
+            .iter()
+            .map(|key| self.add_prefix(key))
+            .collect::<Vec<String>>();
+
+        let output: serde_json::Value = match self
+            .pool
+            .evalsha(hash, keys.to_owned(), args.to_owned())
+            .await
+        {
+            Err(err) if err.details().starts_with("NOSCRIPT") => {
+                self.pool.eval(script, keys, args).await
+            }
+            output => output,
+        }
+        .map_err(|err| RedisError::from_fred(err, RedisError::EvalFailed))?;
+
+        serde_json::from_value(output)
+            .map_err(|err| RedisError::DeserializationError(err.to_string()))
+    }
+
+    /// Sets a JSON value at a path of a document stored with the RedisJSON module (`JSON.SET`).
+    ///
+    /// The value is serialized to JSON, so parts of a document can be updated without reading it back first.
+    ///
+    /// # Type Parameters
+    /// - `T` - The type of the value. Must implement `Serialize`.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key of the document.
+    /// - `path: &str` - The JSONPath at which the value is set, e.g. `$` for the whole document.
+    /// - `value: &T` - The value to set.
+    ///
+    /// # Returns
+    /// - `Result<(), RedisError>` - `Ok(())` if the value was set, or an `RedisError::JsonSetFailed` (`Err`) if the
+    ///   operation fails, including when the RedisJSON module is not loaded.
+    pub async fn json_set<T>(&self, key: &str, path: &str, value: &T) -> Result<(), RedisError>
+    where
+        T: Serialize,
+    {
+        let _timer = redis_operation_timer("json_set");
+        let key = &self.add_prefix(key);
+
+        let serialized_value = serde_json::to_string(value)
+            .map_err(|err| RedisError::SerializationError(err.to_string()))?;
+
+        self.pool
+            .custom::<RedisValue, _>(
+                CustomCommand::new_static("JSON.SET", ClusterHash::FirstKey, false),
+                vec![key.to_owned(), path.to_owned(), serialized_value],
+            )
+            .await
+            .map_err(|err| json_module_error(err, RedisError::JsonSetFailed))?;
+
+        Ok(())
+    }
+
+    /// Gets the JSON value at a path of a document stored with the RedisJSON module (`JSON.GET`).
+    ///
+    /// Note: With JSONPath syntax (paths starting with `$`) Redis replies with an array of all the matching values,
+    /// so `T` should be a `Vec` of the value type.
+    ///
+    /// # Type Parameters
+    /// - `T` - The type of the value. Must implement `DeserializeOwned`.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key of the document.
+    /// - `path: &str` - The path of the value, e.g. `$.name`.
+    ///
+    /// # Returns
+    /// - `Result<Option<T>, RedisError>` - The deserialized value, or `None` if the document does not exist (`Ok`).
+    ///   Returns an `RedisError::JsonGetFailed` if the operation fails, including when the RedisJSON module is not
+    ///   loaded, or an `RedisError::DeserializationError` if the value cannot be deserialized.
+    pub async fn json_get<T>(&self, key: &str, path: &str) -> Result<Option<T>, RedisError>
+    where
+        T: DeserializeOwned,
+    {
+        let _timer = redis_operation_timer("json_get");
+        let key = &self.add_prefix(key);
+
+        let output: Option<String> = self
+            .pool
+            .custom(
+                CustomCommand::new_static("JSON.GET", ClusterHash::FirstKey, false),
+                vec![key.to_owned(), path.to_owned()],
+            )
+            .await
+            .map_err(|err| json_module_error(err, RedisError::JsonGetFailed))?;
+
+        output
+            .map(|value| {
+                serde_json::from_str::<T>(&value)
+                    .map_err(|err| RedisError::DeserializationError(err.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Checks a request against a token bucket rate limiter shared across all the instances using the same Redis.
+    ///
+    /// The bucket holds up to `max_tokens` tokens and is refilled at `refill_per_sec` tokens per second. Each allowed
+    /// request takes one token. The bucket is updated atomically with a Lua script using the clock of the Redis server,
+    /// and expires once it has been idle long enough to be full again.
+    ///
+    /// # Parameters
+    /// - `key: &str` - The key of the bucket, e.g. identifying the API consumer.
+    /// - `max_tokens: u64` - The capacity of the bucket, i.e. the allowed burst of requests.
+    /// - `refill_per_sec: f64` - The number of tokens added to the bucket per second, i.e. the sustained rate.
+    ///
+    /// # Returns
+    /// - `Result<bool, RedisError>` - Whether the request is allowed (`Ok`). Returns an `RedisError::InvalidInput` if
+    ///   `max_tokens` or `refill_per_sec` is not positive and finite, or an `RedisError::EvalFailed` if the script fails.
+    pub async fn rate_limit(
+        &self,
+        key: &str,
+        max_tokens: u64,
+        refill_per_sec: f64,
+    ) -> Result<bool, RedisError> {
+        let _timer = redis_operation_timer("rate_limit");
+        if max_tokens == 0 || !refill_per_sec.is_finite() || refill_per_sec <= 0.0 {
+            return Err(RedisError::InvalidInput(format!(
+                "Invalid rate limit of {max_tokens} tokens refilled at {refill_per_sec} per second"
+            )));
+        }
+
+        let allowed: i64 = self
+            .eval(
+                RATE_LIMIT_SCRIPT,
+                vec![key.to_string()],
+                vec![max_tokens.to_string(), refill_per_sec.to_string()],
+            )
+            .await?;
+
+        Ok(allowed == 1)
+    }
+
+    /// Checks that Redis is reachable by sending a `PING`, on the migration pool as well if configured.
+    ///
+    /// Commands are queued while a client reconnects, so every `PING` is bounded by `timeout` for the check to
+    /// complete quickly, e.g. in a readiness probe, rather than waiting for the whole reconnect policy.
+    ///
+    /// # Parameters
+    /// - `timeout: Duration` - The maximum time to wait for each `PING`.
+    ///
+    /// # Returns
+    /// - `Result<Duration, RedisError>` - The round trip time of the slowest `PING` (`Ok`), or an `RedisError::PingFailed`
+    ///   (`Err`) as soon as any of the pools fails to respond, or an `RedisError::Timeout` if it does not respond within
+    ///   `timeout`.
+    pub async fn ping(&self, timeout: Duration) -> Result<Duration, RedisError> {
+        let _timer = redis_operation_timer("ping");
+        let ping = |pool: &fred::pool::RedisPool| {
+            let client = pool.next().clone();
+            async move {
+                let start = Instant::now();
+                tokio::time::timeout(timeout, client.ping::<()>())
+                    .await
+                    .map_err(|_| {
+                        RedisError::Timeout(format!("No PING response within {timeout:?}"))
+                    })?
+                    .map(|_| start.elapsed())
+                    .map_err(|err| RedisError::from_fred(err, RedisError::PingFailed))
+            }
+        };
+
+        match &self.migration_pool {
+            Some(migration_pool) => {
+                let (elapsed, migration_elapsed) =
+                    futures::try_join!(ping(&self.pool), ping(migration_pool))?;
+                Ok(elapsed.max(migration_elapsed))
+            }
+            None => ping(&self.pool).await,
+        }
+    }
+}
+
+/// Parses the reply of `ZRANGE ... WITHSCORES` into the members and their scores, in order.
+fn parse_scored_members(output: RedisValue) -> Result<Vec<(String, f64)>, RedisError> {
+    let values = match output {
+        RedisValue::Array(values) => values,
+        RedisValue::Null => return Ok(Vec::new()),
+        case => {
+            return Err(RedisError::ZRangeFailed(format!(
+                "Unexpected RedisValue encountered : {:?}",
+                case
+            )))
+        }
+    };
+
+    // RESP3 replies with a `[member, score]` pair per member, while RESP2 replies with a flat list of members and scores.
+    let values = values
+        .into_iter()
+        .flat_map(|value| match value {
+            RedisValue::Array(pair) => pair,
+            value => vec![value],
+        })
+        .collect::<Vec<RedisValue>>();
+
+    values
+        .chunks(2)
+        .map(|pair| match pair {
+            [member, score] => match (member.as_string(), score.as_f64()) {
+                (Some(member), Some(score)) => Ok((member, score)),
+                _ => Err(RedisError::ZRangeFailed(format!(
+                    "Unexpected RedisValue encountered : {:?}",
+                    pair
+                ))),
+            },
+            case => Err(RedisError::ZRangeFailed(format!(
+                "Unexpected RedisValue encountered : {:?}",
+                case
+            ))),
+        })
+        .collect()
+}
+
+/// Parses the reply of `XREAD`/`XREADGROUP` for a single stream into the ids and the deserialized payloads of entries
+/// written with `stream_add`.
+///
+/// Pending entries which were trimmed or deleted from the stream are returned by `XREADGROUP` with no fields, and are
+/// parsed with a `None` payload so that they can still be acknowledged.
+fn parse_stream_entries<T>(
+    stream: &str,
+    output: RedisValue,
+    error: fn(String) -> RedisError,
+) -> Result<Vec<(String, Option<T>)>, RedisError>
+where
+    T: DeserializeOwned,
+{
+    let entries = match output {
+        RedisValue::Null => return Ok(Vec::new()),
+        RedisValue::Map(output) => output
+            .inner()
+            .into_iter()
+            .find(|(key, _)| key.as_str() == Some(stream))
+            .map(|(_, entries)| entries),
+        RedisValue::Array(mut output) => match output.pop() {
+            Some(RedisValue::Array(mut stream_entries)) if stream_entries.len() == 2 => {
+                stream_entries.pop()
+            }
+            _ => None,
+        },
+        value => {
+            return Err(error(format!(
+                "Unexpected RedisValue encountered : {:?}",
+                value
+            )))
+        }
+    };
+
+    let entries = match entries {
+        Some(RedisValue::Array(entries)) => entries,
+        Some(RedisValue::Null) | None => return Ok(Vec::new()),
+        Some(value) => {
+            return Err(error(format!(
+                "Unexpected RedisValue encountered : {:?}",
+                value
+            )))
+        }
+    };
+
+    entries
+        .into_iter()
+        .map(|entry| match entry {
+            RedisValue::Array(mut entry) if entry.len() == 2 => {
+                let fields = entry.pop();
+                let id = entry.pop().and_then(|id| id.into_string());
+                match (id, fields) {
+                    (Some(id), Some(RedisValue::Array(fields))) => {
+                        let payload = fields
+                            .chunks(2)
+                            .find_map(|field| match field {
+                                [name, value]
+                                    if name.as_str().as_deref() == Some(STREAM_PAYLOAD_FIELD) =>
+                                {
+                                    value.as_str().map(|value| value.to_string())
+                                }
+                                _ => None,
+                            })
+                            .ok_or_else(|| {
+                                RedisError::DeserializationError(format!(
+                                    "No {STREAM_PAYLOAD_FIELD} field found in stream entry {id}"
+                                ))
+                            })?;
+                        let payload = serde_json::from_str::<T>(&payload)
+                            .map_err(|err| RedisError::DeserializationError(err.to_string()))?;
+                        Ok((id, Some(payload)))
+                    }
+                    (Some(id), Some(RedisValue::Null)) => Ok((id, None)),
+                    (id, fields) => Err(error(format!(
+                        "Unexpected RedisValue encountered : {:?} {:?}",
+                        id, fields
+                    ))),
+                }
+            }
+            value => Err(error(format!(
+                "Unexpected RedisValue encountered : {:?}",
+                value
+            ))),
+        })
+        .collect()
+}
+
+/// Maps an error returned for a RedisJSON command, calling out when the module is not loaded.
+fn json_module_error(
+    err: fred::error::RedisError,
+    variant: fn(String) -> RedisError,
+) -> RedisError {
+    if err.details().contains("unknown command") {
+        variant(format!("The RedisJSON module is not loaded : {err}"))
+    } else {
+        RedisError::from_fred(err, variant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream_entry(id: &str, fields: RedisValue) -> RedisValue {
+        RedisValue::Array(vec![id.into(), fields])
+    }
+
+    fn stream_reply(stream: &str, entries: Vec<RedisValue>) -> RedisValue {
+        RedisValue::Array(vec![RedisValue::Array(vec![
+            stream.into(),
+            RedisValue::Array(entries),
+        ])])
+    }
+
+    #[test]
+    fn parse_stream_entries_reads_payloads() {
+        let output = stream_reply(
+            "rides",
+            vec![
+                stream_entry(
+                    "1-0",
+                    RedisValue::Array(vec![STREAM_PAYLOAD_FIELD.into(), "1".into()]),
+                ),
+                stream_entry(
+                    "2-0",
+                    RedisValue::Array(vec![STREAM_PAYLOAD_FIELD.into(), "2".into()]),
+                ),
+            ],
+        );
+
+        let entries = parse_stream_entries::<u32>("rides", output, RedisError::XReadFailed);
+
+        assert_eq!(
+            entries.ok(),
+            Some(vec![
+                ("1-0".to_string(), Some(1)),
+                ("2-0".to_string(), Some(2))
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_stream_entries_keeps_trimmed_pending_entries() {
+        let output = stream_reply(
+            "rides",
+            vec![
+                stream_entry("1-0", RedisValue::Null),
+                stream_entry(
+                    "2-0",
+                    RedisValue::Array(vec![STREAM_PAYLOAD_FIELD.into(), "2".into()]),
+                ),
+            ],
+        );
+
+        let entries = parse_stream_entries::<u32>("rides", output, RedisError::XReadGroupFailed);
+
+        assert_eq!(
+            entries.ok(),
+            Some(vec![
+                ("1-0".to_string(), None),
+                ("2-0".to_string(), Some(2))
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_stream_entries_reads_resp3_maps() {
+        let mut output = RedisMap::new();
+        output.insert(
+            "rides".into(),
+            RedisValue::Array(vec![stream_entry(
+                "1-0",
+                RedisValue::Array(vec![STREAM_PAYLOAD_FIELD.into(), "1".into()]),
+            )]),
+        );
+        let output = RedisValue::Map(output);
+
+        let entries = parse_stream_entries::<u32>("rides", output, RedisError::XReadFailed);
+
+        assert_eq!(entries.ok(), Some(vec![("1-0".to_string(), Some(1))]));
+    }
+
+    #[test]
+    fn parse_stream_entries_rejects_missing_payloads() {
+        let output = stream_reply(
+            "rides",
+            vec![stream_entry(
+                "1-0",
+                RedisValue::Array(vec!["other".into(), "1".into()]),
+            )],
+        );
+
+        let entries = parse_stream_entries::<u32>("rides", output, RedisError::XReadFailed);
+
+        assert!(matches!(entries, Err(RedisError::DeserializationError(_))));
+    }
+
+    #[test]
+    fn parse_scored_members_reads_resp2_flat_replies() {
+        let output = RedisValue::Array(vec!["a".into(), "1.5".into(), "b".into(), "2".into()]);
+
+        assert_eq!(
+            parse_scored_members(output).ok(),
+            Some(vec![("a".to_string(), 1.5), ("b".to_string(), 2.0)])
+        );
+    }
+
+    #[test]
+    fn parse_scored_members_reads_resp3_pairs() {
+        let output = RedisValue::Array(vec![
+            RedisValue::Array(vec!["a".into(), RedisValue::Double(1.5)]),
+            RedisValue::Array(vec!["b".into(), RedisValue::Double(2.0)]),
+        ]);
+
+        assert_eq!(
+            parse_scored_members(output).ok(),
+            Some(vec![("a".to_string(), 1.5), ("b".to_string(), 2.0)])
+        );
+    }
+
+    #[test]
+    fn parse_scored_members_reads_empty_replies() {
+        assert_eq!(
+            parse_scored_members(RedisValue::Null).ok(),
+            Some(Vec::new())
+        );
+        assert_eq!(
+            parse_scored_members(RedisValue::Array(Vec::new())).ok(),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn parse_scored_members_rejects_missing_scores() {
+        let output = RedisValue::Array(vec!["a".into(), "1".into(), "b".into()]);
+
+        assert!(matches!(
+            parse_scored_members(output),
+            Err(RedisError::ZRangeFailed(_))
+        ));
+    }
+
+    #[test]
+    fn parse_stream_entries_reads_empty_replies() {
+        let entries =
+            parse_stream_entries::<u32>("rides", RedisValue::Null, RedisError::XReadFailed);
+
+        assert_eq!(entries.ok(), Some(Vec::new()));
+    }
+
+    /// Replies to every command with the integer `1`.
+    #[derive(Debug)]
+    struct ReplyOne;
+
+    impl fred::mocks::Mocks for ReplyOne {
+        fn process_command(
+            &self,
+            _: fred::mocks::MockCommand,
+        ) -> Result<RedisValue, fred::error::RedisError> {
+            Ok(RedisValue::Integer(1))
+        }
+    }
+
+    #[tokio::test]
+    async fn publish_is_timed_once() -> Result<(), RedisError> {
+        let pool = RedisConnectionPool::with_mocks(std::sync::Arc::new(ReplyOne)).await?;
+        let publish = crate::prometheus::REDIS_OPERATION.with_label_values(&["publish"]);
+        let publish_str = crate::prometheus::REDIS_OPERATION.with_label_values(&["publish_str"]);
+        let (before, before_str) = (publish.get_sample_count(), publish_str.get_sample_count());
+
+        assert_eq!(pool.publish("rides", &"ride_started").await?, 1);
+
+        assert_eq!(publish.get_sample_count(), before + 1);
+        assert_eq!(publish_str.get_sample_count(), before_str);
+        Ok(())
+    }
+
+    /// Records the arguments of every command, replying `OK`.
+    #[derive(Debug, Default)]
+    struct RecordArgs(std::sync::Mutex<Vec<Vec<RedisValue>>>);
+
+    impl fred::mocks::Mocks for RecordArgs {
+        fn process_command(
+            &self,
+            command: fred::mocks::MockCommand,
+        ) -> Result<RedisValue, fred::error::RedisError> {
+            if let Ok(mut commands) = self.0.lock() {
+                commands.push(command.args);
+            }
+            Ok(RedisValue::new_ok())
+        }
+    }
+
+    #[tokio::test]
+    async fn set_key_with_default_ttl_expires_after_the_default_ttl() -> Result<(), RedisError> {
+        let mocks = std::sync::Arc::new(RecordArgs::default());
+        let pool = RedisConnectionPool::with_mocks(mocks.clone()).await?;
+
+        pool.set_key_as_str_with_default_ttl("driver", "on_ride")
+            .await?;
+
+        let expiry = mocks
+            .0
+            .lock()
+            .ok()
+            .and_then(|commands| commands.first().map(|args| args[2..].to_vec()));
+        assert_eq!(
+            expiry,
+            Some(vec![
+                "EX".into(),
+                RedisValue::Integer(pool.default_ttl.into())
+            ])
+        );
+        Ok(())
     }
 }
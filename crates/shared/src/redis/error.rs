@@ -24,21 +24,35 @@ pub enum RedisError {
     SerializationError(String),
     DeserializationError(String),
     RedisConnectionError(String),
+    PingFailed(String),
+    Timeout(String),
     SetFailed(String),
     SetExFailed(String),
+    LockFailed(String),
     SetExpiryFailed(String),
+    PersistFailed(String),
+    TtlFailed(String),
     GetFailed(String),
     MGetFailed(String),
+    MSetFailed(String),
+    IncrFailed(String),
+    DecrFailed(String),
     DeleteFailed(String),
+    ExistsFailed(String),
+    ScanFailed(String),
     SetHashFieldFailed(String),
     GetHashFieldFailed(String),
+    JsonSetFailed(String),
+    JsonGetFailed(String),
     RPushFailed(String),
+    LPushFailed(String),
     RPopFailed(String),
     LPopFailed(String),
     LRangeFailed(String),
     LLenFailed(String),
     NotFound(String),
     InvalidRedisEntryId(String),
+    InvalidInput(String),
     SubscribeError(String),
     PublishError(String),
     GeoAddFailed(String),
@@ -48,9 +62,21 @@ pub enum RedisError {
     ZCardFailed(String),
     GeoPosFailed(String),
     ZRangeFailed(String),
+    ZRankFailed(String),
+    ZScoreFailed(String),
+    PfAddFailed(String),
+    PfCountFailed(String),
+    PfMergeFailed(String),
     XAddFailed(String),
     XReadFailed(String),
+    XGroupCreateFailed(String),
+    XReadGroupFailed(String),
+    XAckFailed(String),
+    XPendingFailed(String),
     XDeleteFailed(String),
+    EvalFailed(String),
+    TransactionFailed(String),
+    TransactionAborted(String),
 }
 
 impl RedisError {
@@ -66,21 +92,35 @@ impl RedisError {
             RedisError::SerializationError(err) => err.to_string(),
             RedisError::DeserializationError(err) => err.to_string(),
             RedisError::RedisConnectionError(err) => format!("Redis Connection Error : {err}"),
+            RedisError::PingFailed(err) => format!("Redis Error : {err}"),
+            RedisError::Timeout(err) => format!("Redis Timeout : {err}"),
             RedisError::SetFailed(err) => format!("Redis Error : {err}"),
             RedisError::SetExFailed(err) => format!("Redis Error : {err}"),
+            RedisError::LockFailed(err) => format!("Redis Error : {err}"),
             RedisError::SetExpiryFailed(err) => format!("Redis Error : {err}"),
+            RedisError::PersistFailed(err) => format!("Redis Error : {err}"),
+            RedisError::TtlFailed(err) => format!("Redis Error : {err}"),
             RedisError::GetFailed(err) => format!("Redis Error : {err}"),
             RedisError::MGetFailed(err) => format!("Redis Error : {err}"),
+            RedisError::MSetFailed(err) => format!("Redis Error : {err}"),
+            RedisError::IncrFailed(err) => format!("Redis Error : {err}"),
+            RedisError::DecrFailed(err) => format!("Redis Error : {err}"),
             RedisError::DeleteFailed(err) => format!("Redis Error : {err}"),
+            RedisError::ExistsFailed(err) => format!("Redis Error : {err}"),
+            RedisError::ScanFailed(err) => format!("Redis Error : {err}"),
             RedisError::SetHashFieldFailed(err) => format!("Redis Error : {err}"),
             RedisError::GetHashFieldFailed(err) => format!("Redis Error : {err}"),
+            RedisError::JsonSetFailed(err) => format!("Redis Error : {err}"),
+            RedisError::JsonGetFailed(err) => format!("Redis Error : {err}"),
             RedisError::RPushFailed(err) => format!("Redis Error : {err}"),
+            RedisError::LPushFailed(err) => format!("Redis Error : {err}"),
             RedisError::RPopFailed(err) => format!("Redis Error : {err}"),
             RedisError::LPopFailed(err) => format!("Redis Error : {err}"),
             RedisError::LRangeFailed(err) => format!("Redis Error : {err}"),
             RedisError::LLenFailed(err) => format!("Redis Error : {err}"),
             RedisError::NotFound(err) => format!("Redis Error : {err}"),
             RedisError::InvalidRedisEntryId(err) => format!("Redis Error : {err}"),
+            RedisError::InvalidInput(err) => format!("Redis Error : {err}"),
             RedisError::SubscribeError(err) => format!("Redis Error : {err}"),
             RedisError::PublishError(err) => format!("Redis Error : {err}"),
             RedisError::GeoAddFailed(err) => format!("Redis Error : {err}"),
@@ -90,6 +130,18 @@ impl RedisError {
             RedisError::ZCardFailed(err) => format!("Redis Error : {err}"),
             RedisError::GeoPosFailed(err) => format!("Redis Error : {err}"),
             RedisError::ZRangeFailed(err) => format!("Redis Error : {err}"),
+            RedisError::ZRankFailed(err) => format!("Redis Error : {err}"),
+            RedisError::ZScoreFailed(err) => format!("Redis Error : {err}"),
+            RedisError::PfAddFailed(err) => format!("Redis Error : {err}"),
+            RedisError::PfCountFailed(err) => format!("Redis Error : {err}"),
+            RedisError::PfMergeFailed(err) => format!("Redis Error : {err}"),
+            RedisError::XGroupCreateFailed(err) => format!("Redis Error : {err}"),
+            RedisError::XReadGroupFailed(err) => format!("Redis Error : {err}"),
+            RedisError::XAckFailed(err) => format!("Redis Error : {err}"),
+            RedisError::XPendingFailed(err) => format!("Redis Error : {err}"),
+            RedisError::EvalFailed(err) => format!("Redis Error : {err}"),
+            RedisError::TransactionFailed(err) => format!("Redis Error : {err}"),
+            RedisError::TransactionAborted(err) => format!("Redis Error : {err}"),
             _ => "Some Error Occured".to_string(),
         }
     }
@@ -100,20 +152,34 @@ impl RedisError {
             RedisError::DeserializationError(_) => "DESERIALIZATION_ERROR",
             RedisError::SetFailed(_) => "SET_FAILED",
             RedisError::SetExFailed(_) => "SET_EX_FAILED",
+            RedisError::LockFailed(_) => "LOCK_FAILED",
             RedisError::SetExpiryFailed(_) => "SET_EXPIRY_FAILED",
+            RedisError::PersistFailed(_) => "PERSIST_FAILED",
+            RedisError::TtlFailed(_) => "TTL_FAILED",
             RedisError::GetFailed(_) => "GET_FAILED",
             RedisError::MGetFailed(_) => "MGET_FAILED",
+            RedisError::MSetFailed(_) => "MSET_FAILED",
+            RedisError::IncrFailed(_) => "INCR_FAILED",
+            RedisError::DecrFailed(_) => "DECR_FAILED",
             RedisError::DeleteFailed(_) => "DELETE_FAILED",
+            RedisError::ExistsFailed(_) => "EXISTS_FAILED",
+            RedisError::ScanFailed(_) => "SCAN_FAILED",
             RedisError::SetHashFieldFailed(_) => "SETHASHFIELD_FAILED",
             RedisError::GetHashFieldFailed(_) => "GETHASHFIELD_FAILED",
+            RedisError::JsonSetFailed(_) => "JSON_SET_FAILED",
+            RedisError::JsonGetFailed(_) => "JSON_GET_FAILED",
             RedisError::RPushFailed(_) => "RPUSH_FAILED",
+            RedisError::LPushFailed(_) => "LPUSH_FAILED",
             RedisError::RPopFailed(_) => "RPOP_FAILED",
             RedisError::LPopFailed(_) => "LPOP_FAILED",
             RedisError::LRangeFailed(_) => "LRANGE_FAILED",
             RedisError::LLenFailed(_) => "LLEN_FAILED",
             RedisError::NotFound(_) => "NOT_FOUND",
             RedisError::InvalidRedisEntryId(_) => "INVALID_REDIS_ENTRY_ID",
+            RedisError::InvalidInput(_) => "INVALID_INPUT",
             RedisError::RedisConnectionError(_) => "REDIS_CONNECTION_FAILED",
+            RedisError::PingFailed(_) => "PING_FAILED",
+            RedisError::Timeout(_) => "REDIS_TIMEOUT",
             RedisError::SubscribeError(_) => "SUBSCRIBE_FAILED",
             RedisError::PublishError(_) => "PUBLISH_FAILED",
             RedisError::GeoAddFailed(_) => "GEOADD_FAILED",
@@ -123,12 +189,35 @@ impl RedisError {
             RedisError::ZCardFailed(_) => "ZCARD_FAILED",
             RedisError::GeoPosFailed(_) => "GEOPOS_FAILED",
             RedisError::ZRangeFailed(_) => "ZRANGE_FAILED",
+            RedisError::ZRankFailed(_) => "ZRANK_FAILED",
+            RedisError::ZScoreFailed(_) => "ZSCORE_FAILED",
+            RedisError::PfAddFailed(_) => "PFADD_FAILED",
+            RedisError::PfCountFailed(_) => "PFCOUNT_FAILED",
+            RedisError::PfMergeFailed(_) => "PFMERGE_FAILED",
             RedisError::XAddFailed(_) => "XADD_FAILED",
             RedisError::XReadFailed(_) => "XREAD_FAILED",
+            RedisError::XGroupCreateFailed(_) => "XGROUP_CREATE_FAILED",
+            RedisError::XReadGroupFailed(_) => "XREADGROUP_FAILED",
+            RedisError::XAckFailed(_) => "XACK_FAILED",
+            RedisError::XPendingFailed(_) => "XPENDING_FAILED",
             RedisError::XDeleteFailed(_) => "XDEL_FAILED",
+            RedisError::EvalFailed(_) => "EVAL_FAILED",
+            RedisError::TransactionFailed(_) => "TRANSACTION_FAILED",
+            RedisError::TransactionAborted(_) => "TRANSACTION_ABORTED",
         }
         .to_string()
     }
+
+    /// Maps an error returned by fred to the given variant, or to `RedisError::Timeout` if the command timed out.
+    pub(crate) fn from_fred(
+        err: fred::error::RedisError,
+        variant: fn(String) -> RedisError,
+    ) -> Self {
+        match err.kind() {
+            fred::error::RedisErrorKind::Timeout => RedisError::Timeout(err.to_string()),
+            _ => variant(err.to_string()),
+        }
+    }
 }
 
 impl ResponseError for RedisError {
@@ -144,15 +233,28 @@ impl ResponseError for RedisError {
             RedisError::DeserializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::SetFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::SetExFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::LockFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::SetExpiryFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::PersistFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::TtlFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::GetFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::MGetFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::MSetFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::IncrFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::DecrFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::DeleteFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::ExistsFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::ScanFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::SetHashFieldFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::GetHashFieldFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::JsonSetFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::JsonGetFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::NotFound(_) => StatusCode::NOT_FOUND,
             RedisError::InvalidRedisEntryId(_) => StatusCode::BAD_REQUEST,
+            RedisError::InvalidInput(_) => StatusCode::BAD_REQUEST,
             RedisError::RedisConnectionError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::PingFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
             RedisError::SubscribeError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::PublishError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::GeoAddFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -162,14 +264,27 @@ impl ResponseError for RedisError {
             RedisError::ZCardFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::GeoPosFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::ZRangeFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::ZRankFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::ZScoreFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::PfAddFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::PfCountFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::PfMergeFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::RPushFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::LPushFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::RPopFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::LPopFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::LRangeFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::LLenFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::XAddFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::XReadFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::XGroupCreateFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::XReadGroupFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::XAckFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::XPendingFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
             RedisError::XDeleteFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::EvalFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::TransactionFailed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RedisError::TransactionAborted(_) => StatusCode::CONFLICT,
         }
     }
 }
@@ -8,4 +8,5 @@
 
 pub mod commands;
 pub mod error;
+pub mod pipeline;
 pub mod types;
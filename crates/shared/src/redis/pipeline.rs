@@ -0,0 +1,425 @@
+/*  Copyright 2022-23, Juspay India Pvt Ltd
+    This program is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public License
+    as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later version. This program
+    is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+    or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details. You should have received a copy of
+    the GNU Affero General Public License along with this program. If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::prometheus::redis_operation_timer;
+use crate::redis::error::RedisError;
+use crate::redis::types::RedisConnectionPool;
+use fred::{
+    interfaces::{ClientLike, HashesInterface, KeysInterface, TransactionInterface},
+    types::{Expiration, FromRedis, RedisMap, RedisValue},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+
+type ErrorVariant = fn(String) -> RedisError;
+
+enum PipelineCommand {
+    Set {
+        key: String,
+        value: String,
+        expiry: u32,
+    },
+    SetHashFields {
+        key: String,
+        values: RedisMap,
+    },
+    SetExpiry {
+        key: String,
+        seconds: i64,
+    },
+    Delete {
+        key: String,
+    },
+}
+
+pub struct Pipeline;
+
+pub struct Transaction {
+    // The dedicated connection of the transaction, closed once the transaction is executed or dropped.
+    connection: fred::prelude::RedisClient,
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        let connection = self.connection.clone();
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move {
+                let _ = connection.quit().await;
+            });
+        }
+    }
+}
+
+/// A batch of commands queued in order and sent to Redis in a single round trip.
+pub struct RedisBatch<M> {
+    client: fred::prelude::RedisClient,
+    key_prefix: String,
+    commands: Vec<Result<PipelineCommand, RedisError>>,
+    mode: M,
+}
+
+/// A batch of commands sent as a pipeline, created through `RedisConnectionPool::pipeline`.
+///
+/// Every queued command produces exactly one result, so a failing command does not abort the rest of the batch.
+pub type RedisPipeline = RedisBatch<Pipeline>;
+
+/// A batch of commands sent as a `MULTI`/`EXEC` transaction, created through `RedisConnectionPool::transaction`.
+///
+/// The transaction runs on its own connection, on which the watched keys are read before the writes are queued.
+///
+/// Note: In cluster mode all the keys used in a transaction, including the watched keys, must belong to the same hash slot.
+pub type RedisTransaction = RedisBatch<Transaction>;
+
+impl RedisConnectionPool {
+    /// Creates a new pipeline on the next client of the pool.
+    pub fn pipeline(&self) -> RedisPipeline {
+        RedisBatch {
+            client: self.pool.next().clone(),
+            key_prefix: self.key_prefix.to_owned(),
+            commands: Vec::new(),
+            mode: Pipeline,
+        }
+    }
+
+    /// Creates a new transaction on a dedicated connection, watching the given keys for optimistic locking.
+    ///
+    /// The `watch_keys` are sent with `WATCH` right away, so the transaction is aborted if any of them is modified by
+    /// another client between now and its execution. Reads made through the returned transaction, e.g. `get_key`,
+    /// are therefore protected as well.
+    ///
+    /// `WATCH` only applies to the connection it is sent on, so every transaction opens its own connection instead of
+    /// using a multiplexed client of the pool. That connection is not reconnected, so that the watched keys cannot be
+    /// silently lost, and is closed once the transaction is executed or dropped.
+    ///
+    /// Note: Opening that connection costs a round trip, or in cluster mode one connection to every node of the
+    /// cluster, on top of the transaction itself. Prefer `pipeline` for batches that do not need `WATCH` or atomicity.
+    ///
+    /// # Returns
+    /// - `Result<RedisTransaction, RedisError>` - The transaction (`Ok`), or an `RedisError::RedisConnectionError` if
+    ///   the connection cannot be opened, or an `RedisError::TransactionFailed` if the keys cannot be watched.
+    pub async fn transaction(
+        &self,
+        watch_keys: Vec<String>,
+    ) -> Result<RedisTransaction, RedisError> {
+        let client = self.pool.next();
+        let connection = fred::prelude::RedisClient::new(
+            client.client_config(),
+            Some(client.perf_config()),
+            None,
+        );
+        connection.connect();
+        connection
+            .wait_for_connect()
+            .await
+            .map_err(|err| RedisError::RedisConnectionError(err.to_string()))?;
+
+        let transaction = RedisBatch {
+            client: connection.clone(),
+            key_prefix: self.key_prefix.to_owned(),
+            commands: Vec::new(),
+            mode: Transaction { connection },
+        };
+
+        if !watch_keys.is_empty() {
+            let watch_keys = watch_keys
+                .iter()
+                .map(|key| transaction.add_prefix(key))
+                .collect::<Vec<String>>();
+            transaction
+                .client
+                .watch(watch_keys)
+                .await
+                .map_err(|err| RedisError::from_fred(err, RedisError::TransactionFailed))?;
+        }
+
+        Ok(transaction)
+    }
+}
+
+impl<M> RedisBatch<M> {
+    fn add_prefix(&self, key: &str) -> String {
+        format!("{}{}", self.key_prefix, key)
+    }
+
+    /// Queues setting a key to the JSON serialized value with an expiry time, in seconds.
+    pub fn set_key<V>(mut self, key: &str, value: V, expiry: u32) -> Self
+    where
+        V: Serialize,
+    {
+        let command = serde_json::to_string(&value)
+            .map(|value| PipelineCommand::Set {
+                key: self.add_prefix(key),
+                value,
+                expiry,
+            })
+            .map_err(|err| RedisError::SerializationError(err.to_string()));
+        self.commands.push(command);
+        self
+    }
+
+    /// Queues setting a key to the value as is with an expiry time, in seconds.
+    pub fn set_key_as_str(mut self, key: &str, value: &str, expiry: u32) -> Self {
+        self.commands.push(Ok(PipelineCommand::Set {
+            key: self.add_prefix(key),
+            value: value.to_string(),
+            expiry,
+        }));
+        self
+    }
+
+    /// Queues setting multiple fields in a hash.
+    pub fn set_hash_fields<V>(mut self, key: &str, values: V) -> Self
+    where
+        V: TryInto<RedisMap> + Debug,
+        V::Error: Into<fred::error::RedisError>,
+    {
+        let command = values
+            .try_into()
+            .map(|values| PipelineCommand::SetHashFields {
+                key: self.add_prefix(key),
+                values,
+            })
+            .map_err(|err| RedisError::SetHashFieldFailed(err.into().to_string()));
+        self.commands.push(command);
+        self
+    }
+
+    /// Queues setting an expiry time on a key, in seconds.
+    pub fn set_expiry(mut self, key: &str, seconds: i64) -> Self {
+        self.commands.push(Ok(PipelineCommand::SetExpiry {
+            key: self.add_prefix(key),
+            seconds,
+        }));
+        self
+    }
+
+    /// Queues deleting a key.
+    pub fn delete_key(mut self, key: &str) -> Self {
+        self.commands.push(Ok(PipelineCommand::Delete {
+            key: self.add_prefix(key),
+        }));
+        self
+    }
+}
+
+impl RedisPipeline {
+    /// Sends all the queued commands in a single pipeline.
+    ///
+    /// # Returns
+    /// - `Vec<Result<RedisValue, RedisError>>` - The result of every queued command, in the order they were queued.
+    ///   Commands which could not be queued (e.g. due to a serialization failure) are not sent, and their error is
+    ///   returned in their place.
+    pub async fn execute(self) -> Vec<Result<RedisValue, RedisError>> {
+        let _timer = redis_operation_timer("pipeline");
+        let pipeline = self.client.pipeline();
+
+        let mut results = Vec::with_capacity(self.commands.len());
+        let mut queued: Vec<(usize, ErrorVariant)> = Vec::new();
+
+        for command in self.commands {
+            match command {
+                Ok(command) => {
+                    queued.push((results.len(), queue(&pipeline, command).await));
+                    results.push(Ok(RedisValue::Null));
+                }
+                Err(err) => results.push(Err(err)),
+            }
+        }
+
+        if queued.is_empty() {
+            return results;
+        }
+
+        let outputs = pipeline.try_all::<RedisValue>().await;
+
+        if outputs.len() == queued.len() {
+            for ((idx, error), output) in queued.into_iter().zip(outputs) {
+                results[idx] = output.map_err(|err| RedisError::from_fred(err, error));
+            }
+        } else {
+            // The pipeline failed as a whole, so the same error is reported for every command that was sent.
+            let err = match outputs.into_iter().find_map(Result::err) {
+                Some(err) => err.to_string(),
+                None => "Unexpected number of results returned by the pipeline".to_string(),
+            };
+            for (idx, error) in queued {
+                results[idx] = Err(error(err.to_owned()));
+            }
+        }
+
+        results
+    }
+}
+
+impl RedisTransaction {
+    /// Reads the JSON serialized value of a key on the connection of the transaction.
+    ///
+    /// # Returns
+    /// - `Result<Option<T>, RedisError>` - The deserialized value, or `None` if the key does not exist (`Ok`).
+    ///   Returns an `RedisError::GetFailed` if the operation fails, or an `RedisError::DeserializationError` if the
+    ///   value cannot be deserialized.
+    pub async fn get_key<T>(&self, key: &str) -> Result<Option<T>, RedisError>
+    where
+        T: DeserializeOwned,
+    {
+        match self.get_key_as_str(key).await? {
+            Some(value) => serde_json::from_str(&value)
+                .map(Some)
+                .map_err(|err| RedisError::DeserializationError(err.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the value of a key as is on the connection of the transaction.
+    ///
+    /// # Returns
+    /// - `Result<Option<String>, RedisError>` - The value, or `None` if the key does not exist (`Ok`), or an
+    ///   `RedisError::GetFailed` (`Err`) if the operation fails.
+    pub async fn get_key_as_str(&self, key: &str) -> Result<Option<String>, RedisError> {
+        self.client
+            .get(self.add_prefix(key))
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::GetFailed))
+    }
+
+    /// Reads a field of a hash on the connection of the transaction.
+    ///
+    /// # Returns
+    /// - `Result<V, RedisError>` - The value of the field (`Ok`), or an `RedisError::GetHashFieldFailed` (`Err`) if
+    ///   the operation fails.
+    pub async fn get_hash_field<V>(&self, key: &str, field: &str) -> Result<V, RedisError>
+    where
+        V: FromRedis + Unpin + Send + 'static,
+    {
+        self.client
+            .hget(self.add_prefix(key), field)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::GetHashFieldFailed))
+    }
+
+    /// Executes all the queued commands atomically in a `MULTI`/`EXEC` transaction.
+    ///
+    /// # Returns
+    /// - `Result<Vec<RedisValue>, RedisError>` - The result of every queued command, in the order they were queued, or
+    ///   no result if no command was queued, in which case nothing is sent (`Ok`).
+    ///   Returns an `RedisError::TransactionAborted` if a watched key was modified before the transaction was executed,
+    ///   or an `RedisError::TransactionFailed` if the transaction fails. Nothing is sent if any command could not be queued.
+    pub async fn exec(self) -> Result<Vec<RedisValue>, RedisError> {
+        let _timer = redis_operation_timer("transaction");
+        let commands = self
+            .commands
+            .into_iter()
+            .collect::<Result<Vec<PipelineCommand>, RedisError>>()?;
+
+        // fred returns `Null` without sending anything for an empty transaction, which is not an aborted transaction.
+        if commands.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let transaction = self.mode.connection.multi();
+
+        for command in commands {
+            queue(&transaction, command).await;
+        }
+
+        let output: RedisValue = transaction
+            .exec(true)
+            .await
+            .map_err(|err| RedisError::from_fred(err, RedisError::TransactionFailed))?;
+
+        match output {
+            RedisValue::Array(values) => Ok(values),
+            RedisValue::Null => Err(RedisError::TransactionAborted(
+                "Watched keys were modified, transaction aborted".to_string(),
+            )),
+            value => Ok(vec![value]),
+        }
+    }
+}
+
+async fn queue<C>(client: &C, command: PipelineCommand) -> ErrorVariant
+where
+    C: HashesInterface + KeysInterface + Sync,
+{
+    match command {
+        PipelineCommand::Set { key, value, expiry } => {
+            let _ = client
+                .set::<RedisValue, _, _>(
+                    key,
+                    value,
+                    Some(Expiration::EX(expiry.into())),
+                    None,
+                    false,
+                )
+                .await;
+            RedisError::SetFailed
+        }
+        PipelineCommand::SetHashFields { key, values } => {
+            let _ = client.hset::<RedisValue, _, _>(key, values).await;
+            RedisError::SetHashFieldFailed
+        }
+        PipelineCommand::SetExpiry { key, seconds } => {
+            let _ = client.expire::<RedisValue, _>(key, seconds).await;
+            RedisError::SetExpiryFailed
+        }
+        PipelineCommand::Delete { key } => {
+            let _ = client.del::<RedisValue, _>(key).await;
+            RedisError::DeleteFailed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fred::mocks::{MockCommand, Mocks};
+    use std::sync::Arc;
+
+    /// Acknowledges every command, and replies to `EXEC` as Redis does when a watched key was modified.
+    #[derive(Debug)]
+    struct WatchConflict;
+
+    impl Mocks for WatchConflict {
+        fn process_command(&self, _: MockCommand) -> Result<RedisValue, fred::error::RedisError> {
+            Ok(RedisValue::new_ok())
+        }
+
+        fn process_transaction(
+            &self,
+            _: Vec<MockCommand>,
+        ) -> Result<RedisValue, fred::error::RedisError> {
+            Ok(RedisValue::Null)
+        }
+    }
+
+    #[tokio::test]
+    async fn exec_without_commands_is_not_aborted() -> Result<(), RedisError> {
+        let pool = RedisConnectionPool::with_mocks(Arc::new(WatchConflict)).await?;
+
+        let transaction = pool.transaction(vec!["driver".to_string()]).await?;
+
+        assert!(transaction.exec().await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exec_is_aborted_on_watch_conflict() -> Result<(), RedisError> {
+        let pool = RedisConnectionPool::with_mocks(Arc::new(WatchConflict)).await?;
+
+        let transaction = pool
+            .transaction(vec!["driver".to_string()])
+            .await?
+            .set_key_as_str("driver", "on_ride", 60);
+
+        assert!(matches!(
+            transaction.exec().await,
+            Err(RedisError::TransactionAborted(_))
+        ));
+        Ok(())
+    }
+}